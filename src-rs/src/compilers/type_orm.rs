@@ -1,270 +1,1392 @@
-#[path = "../utils/helpers.rs"]
-mod helpers;
+use crate::enums;
+use crate::files;
+use crate::helpers::{self, class_name};
+use crate::inflection;
+use crate::source_map;
+use crate::types;
 
 use serde_json::{json, Value};
 
+// decorators generated from the model, anything else is user code.
+// the first three make a class an entity.
+const ENTITY_DECORATORS: [&str; 7] = [
+    "Entity",
+    "ChildEntity",
+    "ViewEntity",
+    "TableInheritance",
+    "Tree",
+    "Index",
+    "Check",
+];
+const COLUMN_DECORATORS: [&str; 15] = [
+    "Column",
+    "ViewColumn",
+    "Generated",
+    "PrimaryColumn",
+    "PrimaryGeneratedColumn",
+    "Index",
+    "OneToOne",
+    "OneToMany",
+    "ManyToOne",
+    "ManyToMany",
+    "JoinColumn",
+    "JoinTable",
+    "TreeParent",
+    "TreeChildren",
+    "Check",
+];
+// columns whose value typeORM sets, they aren't generated and their properties are merged verbatim.
+const SPECIAL_COLUMN_DECORATORS: [&str; 5] = [
+    "CreateDateColumn",
+    "UpdateDateColumn",
+    "DeleteDateColumn",
+    "VersionColumn",
+    "ObjectIdColumn",
+];
+
+// everything typeORM exports that is used as a decorator.
+const TYPEORM_DECORATORS: [&str; 41] = [
+    "AfterInsert",
+    "AfterLoad",
+    "AfterRecover",
+    "AfterRemove",
+    "AfterSoftRemove",
+    "AfterUpdate",
+    "BeforeInsert",
+    "BeforeRecover",
+    "BeforeRemove",
+    "BeforeSoftRemove",
+    "BeforeUpdate",
+    "Check",
+    "ChildEntity",
+    "Column",
+    "CreateDateColumn",
+    "DeleteDateColumn",
+    "Entity",
+    "EventSubscriber",
+    "Exclusion",
+    "Generated",
+    "Index",
+    "JoinColumn",
+    "JoinTable",
+    "ManyToMany",
+    "ManyToOne",
+    "ObjectIdColumn",
+    "OneToMany",
+    "OneToOne",
+    "PrimaryColumn",
+    "PrimaryGeneratedColumn",
+    "RelationId",
+    "TableInheritance",
+    "Tree",
+    "TreeChildren",
+    "TreeLevelColumn",
+    "TreeParent",
+    "Unique",
+    "UpdateDateColumn",
+    "VersionColumn",
+    "ViewColumn",
+    "ViewEntity",
+];
+
+/**
+Generator settings read from the `options` JSON.
+*/
+struct Settings {
+    inflector: inflection::Inflector,
+    // `enumStyle: "union"` types enum columns with string literal unions instead of TS enums.
+    union_enums: bool,
+    // `dialect`, the database column types are written for, see `types::dialect`.
+    dialect: usize,
+}
+
 /**
 Convert nodes to typeORM syntax.
+
+When `options` holds the `previousSource` the entities were last generated into, and its
+parsed `previousProgram`, the entities are merged into that source instead (see `merge_entities`).
+With `sourceMap: true` the result is `{ code, sourceMap }`, see `source_map::extract`.
+With `output: "files"` the result is a file map instead, see `entity_files`.
 */
-pub fn convert_to_typeorm(json_str: &str) -> String {
-    let mut entity_codes: Vec<String> = Vec::new();
-    if let Some(json_array) = helpers::parse_json(json_str).as_array_mut() {
-        for data in json_array {
-            let table_name = data["data"]["dbName"].as_str().unwrap_or("Entity");
-            let class_name = data["data"]["name"].as_str().unwrap_or(table_name);
-            let mut entity_code =
-                format!("@Entity({})\nexport class {} {{\n", table_name, class_name);
-
-            let mut column_codes: Vec<String> = Vec::new();
-
-            if let Some(columns) = data["data"]["columns"].as_array() {
-                for column in columns {
-                    let db_name = column["dbName"].as_str().unwrap_or("");
-                    let column_name = column["name"].as_str().unwrap_or(&db_name);
-                    let data_type = column["dataType"].as_str().unwrap_or("string");
-                    let is_primary = column["primaryKey"].as_bool().unwrap_or(false);
-                    let is_index = column["index"].as_bool().unwrap_or(false);
-                    let is_unique = column["unique"].as_bool().unwrap_or(false);
-                    let is_nullable = column["nullable"].as_bool().unwrap_or(false);
-                    let default_value = column["defaultValue"].as_str().unwrap_or("");
-                    let length = column["length"].as_u64();
-                    let precision = column["precision"].as_u64().unwrap_or(0);
-                    let scale = column["scale"].as_u64().unwrap_or(0);
-                    let collation = column["collation"].as_str().unwrap_or("");
-                    let is_auto_increment = column["autoIncrement"].as_bool().unwrap_or(false);
-                    let select = column["select"].as_bool().unwrap_or(true);
-                    let zerofill = column["zerofill"].as_bool().unwrap_or(false);
-                    let column_enum_vec: Vec<String> = column["enum"]
-                        .as_array()
-                        .map(|arr| {
-                            arr.iter()
-                                .filter_map(|val| val.as_str().map(String::from))
-                                .collect()
-                        })
-                        .unwrap_or_else(Vec::new); // Use unwrap_or_else to avoid unnecessary allocation
-                    let column_enum: &Vec<String> = &column_enum_vec; // Reference the stable variable
-                    let column_enum_name = column["enumName"].as_str().unwrap_or("");
-                    let hstore_type = column["hstoreType"].as_str().unwrap_or("").to_string();
-                    let is_array = column["array"].as_bool().unwrap_or(false);
-
-                    // Check for foreign key
-                    let foreign_key = column["foreignKey"].as_object();
-
-                    let ts_data_type = match data_type {
-                        "string" => "string",
-                        "number" => "number",
-                        "date" => "Date",
-                        "json" => "any",
-                        "float" => "number",
-                        "uuid" => "string",
-                        "objectId" => "string",
-                        _ => data_type,
-                    };
+pub fn convert_to_typeorm(json_str: &str, options_str: &str) -> String {
+    let options = helpers::parse_json(options_str);
+    let settings = Settings {
+        inflector: inflection::Inflector::new(&options["inflections"]),
+        union_enums: options["enumStyle"] == "union",
+        dialect: types::dialect(&options),
+    };
 
-                    let mut column_decorator = String::new();
+    let nodes = helpers::parse_json(json_str);
+    let tables: Vec<&Value> = match nodes.as_array() {
+        Some(json_array) => json_array.iter().map(|data| &data["data"]).collect(),
+        None => return String::new(),
+    };
 
-                    if let Some(fk) = foreign_key {
-                        // Extract foreign key details
-                        let target_table = fk["target"]["tableName"].as_str().unwrap_or("");
-                        let target_column = fk["target"]["columnName"].as_str().unwrap_or("");
-                        let on_delete = fk["onDelete"].as_str().unwrap_or("SET NULL");
-                        let on_update = fk["onUpdate"].as_str().unwrap_or("CASCADE");
-                        let mut join_type = fk["type"].as_str().unwrap_or("");
+    if options["output"] == "files" {
+        return entity_files(&tables, &options, &settings);
+    }
 
-                        if join_type == "one-to-one" {
-                            join_type = "OneToOne"
-                        }
-                        if join_type == "one-to-many" {
-                            join_type = "OneToMany"
-                        }
-                        if join_type == "many-to-one" {
-                            join_type = "ManyToOne"
-                        }
+    let code = match options["previousSource"].as_str() {
+        // ranges are required to find the managed code again.
+        Some(source) if options["previousProgram"]["range"].is_array() => {
+            let program = &options["previousProgram"];
+            let merged = merge_entities(source, program, &tables, &settings);
+            let decorators = tables_decorators(&tables, &settings);
+            match previous_typeorm_import(source, program) {
+                // the import is kept verbatim by the merge, update it in place.
+                Some((import_code, specifiers)) => {
+                    merged.replacen(&import_code, &typeorm_import(&decorators, &specifiers), 1)
+                }
+                None => with_import(&typeorm_import(&decorators, &[]), &merged),
+            }
+        }
+        _ => {
+            // enums are used by the decorators, so they are declared first.
+            let codes: Vec<String> = enums::declarations(&tables, settings.union_enums)
+                .into_iter()
+                .map(|(_, code)| code)
+                .chain(
+                    helpers::declaration_order(&tables)
+                        .iter()
+                        .map(|table| entity_code(table, &settings)),
+                )
+                .collect();
+            let code = codes.join("\n\n");
+            let decorators = tables_decorators(&tables, &settings);
+            with_import(&typeorm_import(&decorators, &[]), &code)
+        }
+    };
 
-                        if join_type != "many-to-many" {
-                            // Join Decorator
-                            column_decorator.push_str(&format!(
-                                    "@{}(() => {}, ({}) => {}.{}, {{ onDelete: \"{}\", onUpdate: \"{}\" }})\n",
-                                    join_type,
-                                    target_table,
-                                    target_table.to_lowercase(),
-                                    target_table.to_lowercase(),
-                                    target_column,
-                                    on_delete,
-                                    on_update
-                                ));
-                            // Column Options
-                            column_decorator
-                                .push_str(&format!("    @JoinTable({{ name: \"{}\" }})", db_name));
-                        } else {
-                            // Join table options
-                            // column_decorator.push_str(&format!(
-                            //         "@ManyToMany(() => {}, ({}) => {}.{}, {{ onDelete: \"{}\", onUpdate: \"{}\" }})\n",
-                            //         target_table,
-                            //         target_table.to_lowercase(),
-                            //         target_table.to_lowercase(),
-                            //         target_column,
-                            //         on_delete,
-                            //         on_update
-                            //     ));
-
-                            // let through = fk["through"]
-                            //     .as_str()
-                            //     .unwrap_or(&format!(
-                            //         "{}_{}",
-                            //         table_name.to_lowercase(),
-                            //         target_table.to_lowercase()
-                            //     ))
-                            //     .to_string();
-
-                            // column_decorator.push_str(&format!(
-                            //     "    @JoinTable({{ name: \"{}\", referencedColumnName: \"{}\" }})",
-                            //     through, target_column
-                            // ));
-                        }
-                        if is_index {
-                            column_decorator.push_str("\n    @Index()");
-                        }
-                        // Generate the column definition
-                        entity_code.push_str(&format!(
-                            "    {}\n    {}: {};\n\n",
-                            column_decorator, column_name, target_table
-                        ));
-                    } else if is_primary {
-                        // Handle primary key or auto increment
-                        column_decorator = if data_type == "uuid" {
-                            "@PrimaryGeneratedColumn(\"uuid\")".to_string()
-                        } else {
-                            "@PrimaryGeneratedColumn()".to_string()
-                        };
-                        if is_index {
-                            column_decorator.push_str("\n    @Index()");
-                        }
-                        // Generate the column definition
-                        entity_code.push_str(&format!(
-                            "    {}\n    {}: {};\n\n",
-                            column_decorator, column_name, ts_data_type
-                        ));
-                    } else if is_auto_increment {
-                        column_decorator = "@PrimaryGeneratedColumn(\"increment\")".to_string();
-                        if is_index {
-                            column_decorator.push_str("\n    @Index()");
-                        }
-                        // Generate the column definition
-                        entity_code.push_str(&format!(
-                            "    {}\n    {}: {};\n\n",
-                            column_decorator, column_name, ts_data_type
-                        ));
-                    } else {
-                        // Add @Index() if applicable
-                        if is_index {
-                            column_decorator.push_str("@Index()\n    ");
-                        }
-                        column_decorator.push_str("@Column({ ");
+    let (code, source_map) = source_map::extract(&code);
+    if options["sourceMap"] == true {
+        return helpers::stringify_json(json!({ "code": code, "sourceMap": source_map }));
+    }
+    code
+}
 
-                        if !db_name.is_empty() {
-                            column_decorator.push_str(&format!("name: \"{}\", ", db_name));
-                        }
-                        column_decorator.push_str(&format!("type: \"{}\"", data_type));
-                        if is_unique {
-                            column_decorator.push_str(", unique: true");
-                        }
-                        if is_nullable {
-                            column_decorator.push_str(", nullable: true");
-                        }
-                        if !default_value.is_empty() {
-                            column_decorator.push_str(&format!(", default: \"{}\"", default_value));
-                        }
-                        if let Some(len) = length {
-                            if len != 255 {
-                                column_decorator.push_str(&format!(", length: {}", len));
-                            }
-                        }
-                        if precision > 0 {
-                            column_decorator.push_str(&format!(", precision: {}", precision));
-                        }
-                        if scale > 0 {
-                            column_decorator.push_str(&format!(", scale: {}", scale));
-                        }
-                        if !collation.is_empty() {
-                            column_decorator.push_str(&format!(", collation: \"{}\"", collation));
-                        }
-                        if !select {
-                            column_decorator.push_str(", select: false");
-                        }
-                        /* MySQL Options */
-                        if zerofill {
-                            column_decorator.push_str(", zerofill: true");
-                        }
-                        if column_enum.len() > 0 {
-                            column_decorator.push_str(&format!(
-                                ", enum: \"[\"{}\"]\"",
-                                column_enum.join("\", \"")
-                            ));
-                        }
-                        if !column_enum_name.is_empty() {
-                            column_decorator
-                                .push_str(&format!(", enumName: \"{}\"", column_enum_name));
-                        }
-                        if !hstore_type.is_empty() {
-                            column_decorator
-                                .push_str(&format!(", hstoreType: \"{}\"", hstore_type));
-                        }
-                        if is_array {
-                            column_decorator.push_str(", array: true");
-                        }
+/**
+One file per entity, `{ path: code }`. Each file imports the typeORM decorators it uses and
+the entities it has relations to.
+
+- `fileName`: path template, `{kebab}.entity.ts` by default, see `files::file_name`. Entities
+  mapping to the same path are numbered, see `files::unique_paths`.
+- `barrel: true`: add an `index.ts` re-exporting every entity.
+- `sourceMap: true`: return `{ files, sourceMap }`, with a source map per path.
+*/
+fn entity_files(tables: &[&Value], options: &Value, settings: &Settings) -> String {
+    let template = options["fileName"].as_str().unwrap_or("{kebab}.entity.ts");
+    let paths: Vec<String> = tables
+        .iter()
+        .map(|table| files::file_name(template, class_name(table), table["schema"].as_str()))
+        .collect();
+    // entities mapping to the same path, or to the barrel, get a numbered file each.
+    let reserved: &[&str] = if options["barrel"] == true {
+        &["index.ts"]
+    } else {
+        &[]
+    };
+    let paths = files::unique_paths(paths, reserved);
+
+    // enums are declared in the file of the first entity using them.
+    let empty_array = Vec::new();
+    let enum_types = |table: &Value| -> Vec<String> {
+        table["columns"]
+            .as_array()
+            .unwrap_or(&empty_array)
+            .iter()
+            .filter_map(|column| enums::type_name(column, class_name(table)))
+            .collect()
+    };
+    let enums: Vec<(String, String, usize)> = enums::declarations(tables, settings.union_enums)
+        .into_iter()
+        .map(|(name, code)| {
+            let owner = tables
+                .iter()
+                .position(|table| enum_types(table).contains(&name))
+                .unwrap_or(0);
+            (name, code, owner)
+        })
+        .collect();
+
+    let mut file_map = serde_json::Map::new();
+    let mut source_maps = serde_json::Map::new();
+    for (idx, (table, path)) in tables.iter().zip(&paths).enumerate() {
+        let codes: Vec<String> = enums
+            .iter()
+            .filter(|(_, _, owner)| *owner == idx)
+            .map(|(_, code, _)| code.clone())
+            .chain([entity_code(table, settings)])
+            .collect();
+        let code = codes.join("\n\n");
+
+        // (module, names) imported from other entity files.
+        let mut imported: Vec<(String, Vec<String>)> = Vec::new();
+        let mut import_from = |owner: usize, name: &str| {
+            if owner == idx {
+                return;
+            }
+            let module = files::relative_module(path, &paths[owner]);
+            match imported
+                .iter_mut()
+                .find(|(imported_module, _)| *imported_module == module)
+            {
+                Some((_, names)) if !names.iter().any(|n| n == name) => {
+                    names.push(name.to_string())
+                }
+                Some(_) => {}
+                None => imported.push((module, vec![name.to_string()])),
+            }
+        };
+        for column in table["columns"].as_array().unwrap_or(&empty_array) {
+            let target = column["foreignKey"]["target"]["tableName"]
+                .as_str()
+                .or(column["embedded"]["tableName"].as_str())
+                .unwrap_or("");
+            if let Some(owner) = tables.iter().position(|t| class_name(t) == target) {
+                import_from(owner, target);
+            }
+        }
+        if let Some(parent) = parent_name(table) {
+            if let Some(owner) = tables.iter().position(|t| class_name(t) == parent) {
+                import_from(owner, parent);
+            }
+        }
+        for enum_type in enum_types(table) {
+            if let Some((_, _, owner)) = enums.iter().find(|(name, _, _)| *name == enum_type) {
+                import_from(*owner, &enum_type);
+            }
+        }
+
+        let decorators = table_decorators(table, settings);
+        let mut imports = vec![typeorm_import(&decorators, &[])];
+        for (module, names) in &imported {
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            imports.push(files::import(&names, module));
+        }
+        imports.retain(|import| !import.is_empty());
+
+        let (code, source_map) = source_map::extract(&with_import(&imports.join("\n"), &code));
+        file_map.insert(path.clone(), json!(code));
+        source_maps.insert(path.clone(), json!(source_map));
+    }
+
+    if options["barrel"] == true {
+        file_map.insert("index.ts".to_string(), json!(files::barrel(&paths)));
+    }
+    if options["sourceMap"] == true {
+        return helpers::stringify_json(json!({ "files": file_map, "sourceMap": source_maps }));
+    }
+    helpers::stringify_json(json!(file_map))
+}
+
+/**
+`import { ... } from "typeorm";` for the generated `decorators`, sorted by imported name.
+`specifiers` are the previously imported `(imported, local)` names, they are kept as written
+unless they name a model-managed decorator that isn't generated anymore.
+*/
+fn typeorm_import(decorators: &[String], specifiers: &[(String, String)]) -> String {
+    let mut used: Vec<&str> = Vec::new();
+    for decorator in decorators {
+        let name = decorator_name_of(decorator);
+        if TYPEORM_DECORATORS.contains(&name) && !used.contains(&name) {
+            used.push(name);
+        }
+    }
+
+    let mut names: Vec<(&str, String)> = Vec::new();
+    for (imported, local) in specifiers {
+        let is_managed = ENTITY_DECORATORS.contains(&imported.as_str())
+            || COLUMN_DECORATORS.contains(&imported.as_str());
+        if is_managed && !used.contains(&imported.as_str()) {
+            continue;
+        }
+        names.push(if imported == local {
+            (imported, imported.clone())
+        } else {
+            (imported, format!("{} as {}", imported, local))
+        });
+    }
+    for name in used {
+        // the generated decorators are called by their own name.
+        if !specifiers.iter().any(|(_, local)| local == name) {
+            names.push((name, name.to_string()));
+        }
+    }
+    if names.is_empty() {
+        return String::new();
+    }
+    names.sort();
+    let names: Vec<&str> = names.iter().map(|(_, name)| name.as_str()).collect();
+    files::import(&names, "typeorm")
+}
+
+/**
+Name of a generated decorator, `@Column({ ... })` -> `Column`.
+*/
+fn decorator_name_of(decorator: &str) -> &str {
+    let name = decorator.trim_start_matches('@');
+    name.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or("")
+}
+
+fn with_import(import: &str, code: &str) -> String {
+    if import.is_empty() {
+        return code.to_string();
+    }
+    format!("{}\n\n{}", import, code)
+}
+
+/**
+Source code and `(imported, local)` names of the `"typeorm"` import in `program`, if any.
+*/
+fn previous_typeorm_import(
+    source: &str,
+    program: &Value,
+) -> Option<(String, Vec<(String, String)>)> {
+    let statement = program["body"].as_array()?.iter().find(|statement| {
+        statement["type"] == "ImportDeclaration" && statement["source"]["value"] == "typeorm"
+    })?;
+    let (start, end) = node_range(source, statement);
+    let specifiers = statement["specifiers"]
+        .as_array()?
+        .iter()
+        .filter_map(|specifier| {
+            let imported = specifier["imported"]["name"].as_str()?;
+            let local = specifier["local"]["name"].as_str().unwrap_or(imported);
+            Some((imported.to_string(), local.to_string()))
+        })
+        .collect();
+    Some((source[start..end].to_string(), specifiers))
+}
+
+/**
+A single decorated entity class.
+*/
+fn entity_code(table: &Value, settings: &Settings) -> String {
+    let mut code = class_header(table);
+    code.push(format!(
+        "export {} {{\n{}\n}}",
+        class_declaration(table),
+        column_codes(table, settings).join("\n\n")
+    ));
+    let code = code.join("\n");
+    source_map::mark_table(table["id"].as_str().unwrap_or(""), &code)
+}
+
+/**
+`class Name`, `abstract` for base classes and with the `extends` clause of inheriting tables.
+*/
+fn class_declaration(table: &Value) -> String {
+    let mut declaration = format!("class {}", class_name(table));
+    if table["kind"] == "abstract" {
+        declaration = format!("abstract {}", declaration);
+    }
+    if let Some(parent) = parent_name(table) {
+        declaration = format!("{} extends {}", declaration, parent);
+    }
+    declaration
+}
+
+fn parent_name(table: &Value) -> Option<&str> {
+    table["extends"]["tableName"]
+        .as_str()
+        .filter(|parent| !parent.is_empty())
+}
+
+/**
+Class decorators, preceded by the table description as JSDoc.
+*/
+fn class_header(table: &Value) -> Vec<String> {
+    let mut header = Vec::new();
+    if has_description(table) {
+        header.push(helpers::doc_comment(
+            table["description"].as_str().unwrap_or(""),
+            "",
+        ));
+    }
+    header.extend(class_decorators(table));
+    header
+}
+
+fn class_decorators(table: &Value) -> Vec<String> {
+    let empty_array = Vec::new();
+    let columns = table["columns"].as_array().unwrap_or(&empty_array);
+
+    // embeddables and base classes are plain classes, their columns end up in the tables of
+    // the embedding and inheriting entities.
+    if table["kind"] == "embeddable" || table["kind"] == "abstract" {
+        return Vec::new();
+    }
+
+    // views are read only, they have no indexes or constraints of their own.
+    if table["kind"] == "view" {
+        return vec![view_decorator(table)];
+    }
+
+    // single table inheritance children share the table of their parent.
+    let mut class_decorators = vec![if table["extends"]["strategy"] == "single-table" {
+        child_entity_decorator(table)
+    } else {
+        entity_decorator(table)
+    }];
+    if let Some(tree) = table["tree"].as_str() {
+        class_decorators.push(format!("@Tree({})", helpers::js_string(tree)));
+    }
+    if let Some(inheritance) = table["inheritance"].as_object() {
+        let column = inheritance.get("column").and_then(Value::as_str);
+        let column_type = inheritance.get("type").and_then(Value::as_str);
+        class_decorators.push(format!(
+            "@TableInheritance({{ column: {{ type: {}, name: {} }} }})",
+            helpers::js_string(column_type.unwrap_or("varchar")),
+            helpers::js_string(column.unwrap_or("type"))
+        ));
+    }
+    if let Some(indexes) = table["indexes"].as_array() {
+        for index in indexes {
+            class_decorators.push(index_decorator(index, columns));
+        }
+    }
+    class_decorators.extend(check_decorators(table));
+    class_decorators
+}
+
+/**
+Class and column decorators generated for `table`.
+*/
+fn table_decorators(table: &Value, settings: &Settings) -> Vec<String> {
+    let empty_array = Vec::new();
+    let columns = table["columns"].as_array().unwrap_or(&empty_array);
+    let is_composite_key = has_composite_key(columns);
+    let mut decorators = class_decorators(table);
+    for column in columns {
+        decorators.extend(column_decorators(column, table, is_composite_key, settings).0);
+    }
+    decorators
+}
+
+fn tables_decorators(tables: &[&Value], settings: &Settings) -> Vec<String> {
+    tables
+        .iter()
+        .flat_map(|table| table_decorators(table, settings))
+        .collect()
+}
+
+/**
+More than one key column makes a composite primary key.
+*/
+fn has_composite_key(columns: &[Value]) -> bool {
+    columns
+        .iter()
+        .filter(|col| col["primaryKey"].as_bool().unwrap_or(false) && col["foreignKey"].is_null())
+        .count()
+        > 1
+}
+
+/**
+Code of every column of `table`, in column order, marked for the source map.
+*/
+fn column_codes(table: &Value, settings: &Settings) -> Vec<String> {
+    let empty_array = Vec::new();
+    let columns = table["columns"].as_array().unwrap_or(&empty_array);
+    let is_composite_key = has_composite_key(columns);
+
+    columns
+        .iter()
+        .map(|column| {
+            let code = column_code(column, table, is_composite_key, settings);
+            source_map::mark_column(column["id"].as_str().unwrap_or(""), &code)
+        })
+        .collect()
+}
+
+/**
+Merge the entities into `source`, the code they were previously generated into and the user
+has edited since. `program` is the ESTree of `source`.
+
+Model-managed decorators and decorated properties of existing entity classes are replaced in place,
+properties of removed columns and classes of removed entities are dropped. Everything else, imports,
+comments, methods, hooks, unknown decorators and free-standing code, is kept verbatim.
+Entities new to the model are appended.
+*/
+fn merge_entities(source: &str, program: &Value, tables: &[&Value], settings: &Settings) -> String {
+    let empty_array = Vec::new();
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    let mut merged_classes: Vec<&str> = Vec::new();
+    let mut previous_end = 0;
+
+    let enums = enums::declarations(tables, settings.union_enums);
+    let mut merged_enums: Vec<&str> = Vec::new();
+    // new enums are declared before the first entity, which uses them at definition time.
+    let mut first_class_start: Option<usize> = None;
+
+    for statement in program["body"].as_array().unwrap_or(&empty_array) {
+        let class = if statement["type"] == "ExportNamedDeclaration" {
+            &statement["declaration"]
+        } else {
+            statement
+        };
+        let (start, end) = node_range(source, statement);
+
+        let declaration_type = class["type"].as_str().unwrap_or("");
+        if declaration_type == "TSEnumDeclaration" || declaration_type == "TSTypeAliasDeclaration" {
+            let name = class["id"]["name"].as_str().unwrap_or("");
+            if let Some((name, code)) = enums.iter().find(|(enum_name, _)| enum_name == name) {
+                merged_enums.push(name);
+                edits.push((start, end, code.clone()));
+            }
+        }
 
-                        column_decorator.push_str(" })");
-                        // Generate the column definition
-                        column_codes.push(format!(
-                            "    {}\n    {}: {};",
-                            column_decorator, column_name, ts_data_type
-                        ));
+        if class["type"] == "ClassDeclaration" {
+            let name = class["id"]["name"].as_str().unwrap_or("");
+            let table = tables.iter().find(|table| class_name(table) == name);
+
+            match table {
+                Some(table) if !merged_classes.contains(&name) => {
+                    merged_classes.push(name);
+                    let doc_start = doc_comment_start(source, start).unwrap_or(start);
+                    first_class_start = first_class_start.or(Some(doc_start));
+                    edits.extend(merge_class(source, class, start, table, tables, settings));
+                    edits.push((end, end, source_map::close_region()));
+                }
+                _ if has_decorator(class, &ENTITY_DECORATORS[..3]) => {
+                    // the entity was removed from the model.
+                    edits.push((previous_end, end, String::new()));
+                }
+                _ => {}
+            }
+        }
+        previous_end = end;
+    }
+
+    let new_enums: Vec<&str> = enums
+        .iter()
+        .filter(|(name, _)| !merged_enums.contains(&name.as_str()))
+        .map(|(_, code)| code.as_str())
+        .collect();
+    let new_enums = new_enums.join("\n\n");
+    if let (Some(start), false) = (first_class_start, new_enums.is_empty()) {
+        // pushed last, so it ends up before a decorator inserted at the same position.
+        edits.push((start, start, format!("{}\n\n", new_enums)));
+    }
+
+    // applied back to front, so earlier ranges stay valid.
+    edits.sort_by_key(|edit| std::cmp::Reverse((edit.0, edit.1)));
+    let mut merged = source.to_string();
+    for (start, end, text) in edits {
+        merged.replace_range(start..end, &text);
+    }
+    if first_class_start.is_none() && !new_enums.is_empty() {
+        merged = format!("{}\n\n{}", merged.trim_end(), new_enums);
+    }
+
+    for table in tables {
+        if !merged_classes.contains(&class_name(table)) {
+            merged = format!("{}\n\n{}", merged.trim_end(), entity_code(table, settings));
+        }
+    }
+    merged.trim_start().to_string()
+}
+
+/**
+Edits updating a previously generated entity `class` to `table`.
+`start` is where the class statement, including a possible `export`, begins.
+*/
+fn merge_class(
+    source: &str,
+    class: &Value,
+    start: usize,
+    table: &Value,
+    tables: &[&Value],
+    settings: &Settings,
+) -> Vec<(usize, usize, String)> {
+    let empty_array = Vec::new();
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+
+    // class decorators, user decorators are kept after the generated ones.
+    // the table's source map region opens with them and is closed by the caller.
+    // a description replaces the JSDoc above the class, otherwise the user's is kept.
+    let mut decorators = class_header(table);
+    if has_description(table) {
+        if let Some(doc_start) = doc_comment_start(source, start) {
+            edits.push((doc_start, start, String::new()));
+        }
+    }
+    let previous_decorators = class["decorators"].as_array().unwrap_or(&empty_array);
+    for decorator in previous_decorators {
+        if !ENTITY_DECORATORS.contains(&decorator_name(decorator)) {
+            let (decorator_start, decorator_end) = node_range(source, decorator);
+            decorators.push(source[decorator_start..decorator_end].to_string());
+        }
+    }
+    match (previous_decorators.first(), previous_decorators.last()) {
+        (Some(first), Some(last)) => {
+            let (first_start, _) = node_range(source, first);
+            let (_, mut last_end) = node_range(source, last);
+            if decorators.is_empty() {
+                // no decorators left, e.g. an entity turned embeddable.
+                last_end = source.len() - source[last_end..].trim_start().len();
+            }
+            let open = source_map::open_table(table["id"].as_str().unwrap_or(""));
+            edits.push((first_start, last_end, open + &decorators.join("\n")));
+        }
+        _ if decorators.is_empty() => {
+            edits.push((
+                start,
+                start,
+                source_map::open_table(table["id"].as_str().unwrap_or("")),
+            ));
+        }
+        _ => {
+            let open = source_map::open_table(table["id"].as_str().unwrap_or(""));
+            edits.push((start, start, format!("{}{}\n", open, decorators.join("\n"))));
+        }
+    }
+
+    // the `extends` clause, rewritten when the parent changed. Parents outside the model,
+    // e.g. typeORM's `BaseEntity`, are the user's.
+    let (body_start, _) = node_range(source, &class["body"]);
+    let previous_parent = class["superClass"]["name"].as_str();
+    let is_model_parent = previous_parent
+        .is_some_and(|parent| tables.iter().any(|table| class_name(table) == parent));
+    if previous_parent != parent_name(table) && (is_model_parent || parent_name(table).is_some()) {
+        let (_, id_end) = node_range(source, &class["id"]);
+        let heritage = match parent_name(table) {
+            Some(parent) => format!(" extends {} ", parent),
+            None => " ".to_string(),
+        };
+        edits.push((id_end, body_start, heritage));
+    }
+
+    // class members.
+    let columns = table["columns"].as_array().unwrap_or(&empty_array);
+    let codes = column_codes(table, settings);
+    let mut merged_columns = vec![false; columns.len()];
+
+    let mut previous_end = body_start + 1;
+    let mut insert_at: Option<usize> = None;
+
+    for member in class["body"]["body"].as_array().unwrap_or(&empty_array) {
+        let (member_start, member_end) = node_range(source, member);
+        let is_property = member["type"] == "PropertyDefinition";
+        let name = member["key"]["name"].as_str().unwrap_or("");
+        let column_idx = columns.iter().enumerate().position(|(idx, col)| {
+            is_property && !merged_columns[idx] && col["name"].as_str() == Some(name)
+        });
+
+        if let Some(column_idx) =
+            column_idx.filter(|_| has_decorator(member, &SPECIAL_COLUMN_DECORATORS))
+        {
+            merged_columns[column_idx] = true;
+            let id = columns[column_idx]["id"].as_str().unwrap_or("");
+            let code = &source[member_start..member_end];
+            edits.push((member_start, member_end, source_map::mark_column(id, code)));
+            insert_at = Some(member_end);
+        } else if let Some(column_idx) = column_idx {
+            merged_columns[column_idx] = true;
+            let mut lines: Vec<String> = Vec::new();
+            for decorator in member["decorators"].as_array().unwrap_or(&empty_array) {
+                if !COLUMN_DECORATORS.contains(&decorator_name(decorator)) {
+                    let (decorator_start, decorator_end) = node_range(source, decorator);
+                    lines.push(format!("    {}", &source[decorator_start..decorator_end]));
+                }
+            }
+            let code = &codes[column_idx];
+            let mut member_start = member_start;
+            let merged_code = if has_description(&columns[column_idx]) {
+                member_start = doc_comment_start(source, member_start).unwrap_or(member_start);
+                // user decorators go between the generated JSDoc and decorators.
+                let doc_end = code.find("*/\n").map_or(0, |idx| idx + 3);
+                lines.insert(0, code[..doc_end].trim_end().to_string());
+                lines.push(code[doc_end..].to_string());
+                lines.join("\n")
+            } else {
+                lines.push(code.clone());
+                lines.join("\n")
+            };
+            edits.push((
+                member_start,
+                member_end,
+                merged_code.trim_start().to_string(),
+            ));
+            insert_at = Some(member_end);
+        } else if is_property
+            && (has_decorator(member, &COLUMN_DECORATORS)
+                || has_decorator(member, &SPECIAL_COLUMN_DECORATORS))
+        {
+            // the column was removed from the model.
+            edits.push((previous_end, member_end, String::new()));
+        }
+        previous_end = member_end;
+    }
+
+    let new_codes: Vec<String> = codes
+        .into_iter()
+        .zip(merged_columns)
+        .filter(|(_, merged)| !merged)
+        .map(|(code, _)| code)
+        .collect();
+    if !new_codes.is_empty() {
+        match insert_at {
+            Some(insert_at) => edits.push((
+                insert_at,
+                insert_at,
+                format!("\n\n{}", new_codes.join("\n\n")),
+            )),
+            None => edits.push((
+                body_start + 1,
+                body_start + 1,
+                format!("\n{}\n", new_codes.join("\n\n")),
+            )),
+        }
+    }
+    edits
+}
+
+fn decorator_name(decorator: &Value) -> &str {
+    let expression = &decorator["expression"];
+    expression["callee"]["name"]
+        .as_str()
+        .or(expression["name"].as_str())
+        .unwrap_or("")
+}
+
+fn has_decorator(node: &Value, names: &[&str]) -> bool {
+    node["decorators"]
+        .as_array()
+        .map(|decorators| {
+            decorators
+                .iter()
+                .any(|d| names.contains(&decorator_name(d)))
+        })
+        .unwrap_or(false)
+}
+
+/**
+Whether a table or column has a non-blank description to write as JSDoc.
+*/
+fn has_description(item: &Value) -> bool {
+    item["description"]
+        .as_str()
+        .is_some_and(|description| !description.trim().is_empty())
+}
+
+/**
+Start of the JSDoc comment directly above the code starting at `start`, if there is one.
+*/
+fn doc_comment_start(source: &str, start: usize) -> Option<usize> {
+    let before = source[..start].trim_end();
+    if !before.ends_with("*/") {
+        return None;
+    }
+    let doc_start = before.rfind("/**")?;
+    if before[doc_start + 3..before.len() - 2].contains("*/") {
+        return None;
+    }
+    Some(doc_start)
+}
+
+/**
+Byte range of an ESTree node in `source`. Decorators written before `export` or a class member
+are included.
+*/
+fn node_range(source: &str, node: &Value) -> (usize, usize) {
+    let offset = |value: &Value| helpers::byte_offset(source, value.as_u64().unwrap_or(0) as usize);
+    let mut start = offset(&node["range"][0]);
+    let end = offset(&node["range"][1]);
+
+    let decorated = if node["type"] == "ExportNamedDeclaration" {
+        &node["declaration"]
+    } else {
+        node
+    };
+    if let Some(decorators) = decorated["decorators"].as_array() {
+        for decorator in decorators {
+            start = start.min(offset(&decorator["range"][0]));
+        }
+    }
+    (start, end)
+}
+
+/**
+Decorators and property definition of a single column, indented for the class body.
+*/
+fn column_code(
+    column: &Value,
+    table: &Value,
+    is_composite_key: bool,
+    settings: &Settings,
+) -> String {
+    let db_name = column["dbName"].as_str().unwrap_or("");
+    let column_name = column["name"].as_str().unwrap_or(db_name);
+    let (decorators, property_type) = column_decorators(column, table, is_composite_key, settings);
+
+    let mut code: Vec<String> = Vec::new();
+    if let Some(description) = column["description"]
+        .as_str()
+        .filter(|d| !d.trim().is_empty())
+    {
+        code.push(helpers::doc_comment(description, "    "));
+    }
+    code.extend(
+        decorators
+            .iter()
+            .map(|decorator| format!("    {}", decorator)),
+    );
+    code.push(format!("    {}: {};", column_name, property_type));
+    code.join("\n")
+}
+
+/**
+Decorators of a single column and the TS type of its property.
+*/
+fn column_decorators(
+    column: &Value,
+    table: &Value,
+    is_composite_key: bool,
+    settings: &Settings,
+) -> (Vec<String>, String) {
+    let class_name = class_name(table);
+    let db_name = column["dbName"].as_str().unwrap_or("");
+    let column_name = column["name"].as_str().unwrap_or(db_name);
+    let data_type = column["dataType"].as_str().unwrap_or("string");
+    let is_primary = column["primaryKey"].as_bool().unwrap_or(false);
+    let is_index = column["index"].as_bool().unwrap_or(false);
+    let is_auto_increment = column["autoIncrement"].as_bool().unwrap_or(false);
+
+    let ts_data_type = types::ts_type(data_type);
+
+    let mut decorators: Vec<String> = Vec::new();
+    let mut property_type = ts_data_type.to_string();
+    if let Some(enum_type) = enums::type_name(column, class_name) {
+        property_type = enum_type;
+    }
+
+    if table["kind"] == "view" {
+        // columns of a view only map a selected column to a property.
+        decorators.push(if !db_name.is_empty() && db_name != column_name {
+            format!("@ViewColumn({{ name: {} }})", helpers::js_string(db_name))
+        } else {
+            "@ViewColumn()".to_string()
+        });
+    } else if let Some(fk) = column["foreignKey"].as_object() {
+        // Extract foreign key details
+        let target_table = fk["target"]["tableName"].as_str().unwrap_or("");
+        let target_column = fk["target"]["columnName"].as_str().unwrap_or("");
+        let on_delete = fk
+            .get("onDelete")
+            .and_then(Value::as_str)
+            .unwrap_or("SET NULL");
+        let on_update = fk
+            .get("onUpdate")
+            .and_then(Value::as_str)
+            .unwrap_or("CASCADE");
+        let join_type = fk.get("type").and_then(Value::as_str).unwrap_or("");
+        let target_variable = inflection::to_camel_case(target_table);
+        let inverse = match target_column {
+            "" => helpers::default_inverse_name(class_name, join_type, &settings.inflector),
+            _ => target_column.to_string(),
+        };
+        // collection sides of a relation are typed as arrays.
+        property_type = if join_type.ends_with("-many") {
+            format!("{}[]", target_table)
+        } else {
+            target_table.to_string()
+        };
+
+        let relation = match join_type {
+            "one-to-one" => "OneToOne",
+            "one-to-many" => "OneToMany",
+            "many-to-many" => "ManyToMany",
+            _ => "ManyToOne",
+        };
+        // self references of a tree table link a node to its parent and children.
+        let is_tree_relation = !table["tree"].is_null()
+            && target_table == class_name
+            && matches!(relation, "ManyToOne" | "OneToMany");
+
+        // Join Decorator
+        decorators.push(match relation {
+            "ManyToOne" if is_tree_relation => {
+                format!("@TreeParent({{ onDelete: \"{}\" }})", on_delete)
+            }
+            "OneToMany" if is_tree_relation => "@TreeChildren()".to_string(),
+            _ => format!(
+                "@{}(() => {}, ({}) => {}.{}, {{ onDelete: \"{}\", onUpdate: \"{}\" }})",
+                relation,
+                target_table,
+                target_variable,
+                target_variable,
+                inverse,
+                on_delete,
+                on_update
+            ),
+        });
+
+        match relation {
+            "ManyToMany" => {
+                // defaults to `<table>_<targets>`, e.g. `post_categories`.
+                let through = fk
+                    .get("through")
+                    .and_then(Value::as_str)
+                    .filter(|through| !through.is_empty())
+                    .map(String::from)
+                    .unwrap_or_else(|| {
+                        helpers::default_join_table_name(
+                            class_name,
+                            target_table,
+                            &settings.inflector,
+                        )
+                    });
+                // the join table lives next to the owning table.
+                let mut join_options = vec![format!("name: {}", helpers::js_string(&through))];
+                for key in ["schema", "database"] {
+                    if let Some(value) = table[key].as_str().filter(|value| !value.is_empty()) {
+                        join_options.push(format!("{}: {}", key, helpers::js_string(value)));
                     }
                 }
-                let joined_column_codes = column_codes.join("\n\n");
+                decorators.push(format!("@JoinTable({{ {} }})", join_options.join(", ")));
+            }
+            "OneToMany" => {
+                // the foreign key lives on the other side, there is no join column.
+            }
+            _ => {
+                // owning side of the relation holds the foreign key column.
+                let mut join_options: Vec<String> = Vec::new();
+                if !db_name.is_empty() {
+                    join_options.push(format!("name: {}", helpers::js_string(db_name)));
+                }
+                if !target_column.is_empty() {
+                    join_options.push(format!(
+                        "referencedColumnName: {}",
+                        helpers::js_string(target_column)
+                    ));
+                }
+                decorators.push(format!("@JoinColumn({{ {} }})", join_options.join(", ")));
+            }
+        }
+    } else if let Some(embedded_type) = column["embedded"]["tableName"].as_str() {
+        // the columns of an embeddable class, prefixed with the property name by default.
+        property_type = embedded_type.to_string();
+        let prefix = match &column["embedded"]["prefix"] {
+            Value::String(prefix) => format!(", {{ prefix: {} }}", helpers::js_string(prefix)),
+            Value::Bool(false) => ", { prefix: false }".to_string(),
+            _ => String::new(),
+        };
+        decorators.push(format!("@Column(() => {}{})", embedded_type, prefix));
+    } else if is_primary {
+        // Handle primary key or auto increment
+        let strategy = helpers::generation_strategy(column, is_composite_key);
+        let mut key_options: Vec<String> = Vec::new();
+        if !db_name.is_empty() && db_name != column_name {
+            key_options.push(format!("name: {}", helpers::js_string(db_name)));
+        }
+        // generated keys are integers, or uuids with the uuid strategy, unless e.g. a bigint.
+        let implied_type = match strategy.as_deref() {
+            Some("uuid") => "uuid",
+            _ => "number",
+        };
+        if data_type != implied_type && !data_type.is_empty() {
+            key_options.push(format!(
+                "type: {}",
+                helpers::js_string(types::native_type(data_type, settings.dialect))
+            ));
+        }
+        if let Some(description) = column["description"]
+            .as_str()
+            .filter(|d| !d.trim().is_empty())
+        {
+            key_options.push(format!("comment: {}", helpers::js_string(description)));
+        }
+        if strategy.as_deref() == Some("identity") {
+            key_options.extend(identity_option(column));
+        }
+        let key_options = if key_options.is_empty() {
+            String::new()
+        } else {
+            format!("{{ {} }}", key_options.join(", "))
+        };
+
+        decorators.push(match strategy {
+            Some(strategy) if strategy == "increment" => {
+                format!("@PrimaryGeneratedColumn({})", key_options)
+            }
+            Some(strategy) if key_options.is_empty() => {
+                format!("@PrimaryGeneratedColumn(\"{}\")", strategy)
+            }
+            Some(strategy) => {
+                format!("@PrimaryGeneratedColumn(\"{}\", {})", strategy, key_options)
+            }
+            // natural or composite keys are assigned by the application.
+            None => format!(
+                "@PrimaryColumn({{ {} }})",
+                column_options(column, class_name, settings).join(", ")
+            ),
+        });
+    } else if is_auto_increment {
+        decorators.push("@PrimaryGeneratedColumn(\"increment\")".to_string());
+    } else {
+        decorators.push(format!(
+            "@Column({{ {} }})",
+            column_options(column, class_name, settings).join(", ")
+        ));
+        // values generated by the database for columns that aren't the key, e.g. a uuid or a serial.
+        if let Some(strategy) = column["generated"].as_str().filter(|s| !s.is_empty()) {
+            decorators.push(format!("@Generated({})", helpers::js_string(strategy)));
+        }
+    }
 
-                entity_code.push_str(&format!("{}", joined_column_codes));
-                entity_code.push('}');
+    // Add @Index() if applicable
+    if is_index {
+        decorators.push("@Index()".to_string());
+    }
+    decorators.extend(check_decorators(column));
+    (decorators, property_type)
+}
+
+/**
+`generatedIdentity` option of an identity column, `GENERATED ALWAYS` or `BY DEFAULT AS IDENTITY`.
+*/
+fn identity_option(column: &Value) -> Option<String> {
+    column["identityGeneration"]
+        .as_str()
+        .filter(|generation| !generation.is_empty())
+        .map(|generation| format!("generatedIdentity: {}", helpers::js_string(generation)))
+}
+
+/**
+Options of a `@Column` / `@PrimaryColumn`, e.g. `name: "first_name", type: "string", nullable: true`.
+*/
+fn column_options(column: &Value, class_name: &str, settings: &Settings) -> Vec<String> {
+    let db_name = column["dbName"].as_str().unwrap_or("");
+    let data_type = column["dataType"].as_str().unwrap_or("string");
+    let is_primary = column["primaryKey"].as_bool().unwrap_or(false);
+    let is_unique = column["unique"].as_bool().unwrap_or(false);
+    let is_nullable = column["nullable"].as_bool().unwrap_or(false);
+    let default_value = helpers::typed_default(column);
+    let length = column["length"].as_u64();
+    let precision = column["precision"].as_u64().unwrap_or(0);
+    let scale = column["scale"].as_u64().unwrap_or(0);
+    let collation = column["collation"].as_str().unwrap_or("");
+    let select = column["select"].as_bool().unwrap_or(true);
+    let zerofill = column["zerofill"].as_bool().unwrap_or(false);
+    let column_enum: Vec<String> = column["enum"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|val| val.as_str().map(helpers::js_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let column_enum_name = column["enumName"].as_str().unwrap_or("");
+    let hstore_type = column["hstoreType"].as_str().unwrap_or("");
+    let is_array = column["array"].as_bool().unwrap_or(false);
+
+    let mut options: Vec<String> = Vec::new();
+
+    if !db_name.is_empty() {
+        options.push(format!("name: {}", helpers::js_string(db_name)));
+    }
+    options.push(format!(
+        "type: {}",
+        helpers::js_string(types::native_type(data_type, settings.dialect))
+    ));
+    // primary keys are implicitly unique and not nullable.
+    if is_unique && !is_primary {
+        options.push("unique: true".to_string());
+    }
+    if is_nullable && !is_primary {
+        options.push("nullable: true".to_string());
+    }
+    if let Some(expression) = helpers::default_expression(column) {
+        options.push(format!(
+            "default: () => {}",
+            helpers::js_string(&expression)
+        ));
+    } else if !default_value.is_null() {
+        options.push(format!("default: {}", helpers::js_literal(&default_value)));
+    }
+    if let Some(len) = length {
+        if len != 255 {
+            options.push(format!("length: {}", len));
+        }
+    }
+    if precision > 0 {
+        options.push(format!("precision: {}", precision));
+    }
+    if scale > 0 {
+        options.push(format!("scale: {}", scale));
+    }
+    if !collation.is_empty() {
+        options.push(format!("collation: {}", helpers::js_string(collation)));
+    }
+    if !select {
+        options.push("select: false".to_string());
+    }
+    /* MySQL Options */
+    if zerofill {
+        options.push("zerofill: true".to_string());
+    }
+    if !column_enum.is_empty() {
+        match enums::type_name(column, class_name) {
+            Some(enum_type) if !settings.union_enums => {
+                options.push(format!("enum: {}", enum_type))
             }
-            entity_codes.push(entity_code)
+            // a union type has no runtime value, the values are listed.
+            _ => options.push(format!("enum: [{}]", column_enum.join(", "))),
+        }
+    }
+    if !column_enum_name.is_empty() {
+        options.push(format!(
+            "enumName: {}",
+            helpers::js_string(column_enum_name)
+        ));
+    }
+    if !hstore_type.is_empty() {
+        options.push(format!("hstoreType: {}", helpers::js_string(hstore_type)));
+    }
+    if let Some(description) = column["description"]
+        .as_str()
+        .filter(|d| !d.trim().is_empty())
+    {
+        options.push(format!("comment: {}", helpers::js_string(description)));
+    }
+    if is_array {
+        options.push("array: true".to_string());
+    }
+    if column["generated"] == "identity" {
+        options.extend(identity_option(column));
+    }
+    options
+}
+
+/**
+`@Check([name,] expression)` of every check constraint of a table or column.
+*/
+fn check_decorators(item: &Value) -> Vec<String> {
+    let empty_array = Vec::new();
+    item["checks"]
+        .as_array()
+        .unwrap_or(&empty_array)
+        .iter()
+        .filter_map(|check| {
+            let expression = check["expression"].as_str().filter(|e| !e.is_empty())?;
+            Some(
+                match check["name"].as_str().filter(|name| !name.is_empty()) {
+                    Some(name) => format!(
+                        "@Check({}, {})",
+                        helpers::js_string(name),
+                        helpers::js_string(expression)
+                    ),
+                    None => format!("@Check({})", helpers::js_string(expression)),
+                },
+            )
+        })
+        .collect()
+}
+
+/**
+`@ChildEntity()` of a single table inheritance child, with its discriminator value if it has one.
+*/
+fn child_entity_decorator(table: &Value) -> String {
+    match table["extends"]["discriminator"].as_str() {
+        Some(value) if !value.is_empty() => {
+            format!("@ChildEntity({})", helpers::js_string(value))
         }
+        _ => "@ChildEntity()".to_string(),
+    }
+}
+
+/**
+Class-level `@Entity`, e.g. `@Entity({ name: "users", schema: "auth" })`.
+*/
+fn entity_decorator(table: &Value) -> String {
+    let mut entity_options: Vec<String> = Vec::new();
+
+    for key in ["dbName", "schema", "database"] {
+        if let Some(value) = table[key].as_str().filter(|value| !value.is_empty()) {
+            let option = if key == "dbName" { "name" } else { key };
+            entity_options.push(format!("{}: {}", option, helpers::js_string(value)));
+        }
+    }
+    // InnoDB is the MySQL default and not worth repeating on every entity.
+    if let Some(engine) = table["engine"]
+        .as_str()
+        .filter(|engine| !engine.is_empty() && *engine != "InnoDB")
+    {
+        entity_options.push(format!("engine: {}", helpers::js_string(engine)));
+    }
+    if table["synchronize"] == false {
+        entity_options.push("synchronize: false".to_string());
+    }
+    if let Some(description) = table["description"]
+        .as_str()
+        .filter(|d| !d.trim().is_empty())
+    {
+        entity_options.push(format!("comment: {}", helpers::js_string(description)));
+    }
+
+    let empty_array = Vec::new();
+    let columns = table["columns"].as_array().unwrap_or(&empty_array);
+    let order_by: Vec<String> = table["orderBy"]
+        .as_array()
+        .unwrap_or(&empty_array)
+        .iter()
+        .filter_map(|order| {
+            let column = columns.iter().find(|col| col["id"] == order["column"])?;
+            Some(format!(
+                "{}: {}",
+                column["name"].as_str().unwrap_or(""),
+                helpers::js_string(order["order"].as_str().unwrap_or("ASC"))
+            ))
+        })
+        .collect();
+    if !order_by.is_empty() {
+        entity_options.push(format!("orderBy: {{ {} }}", order_by.join(", ")));
+    }
+
+    if entity_options.is_empty() {
+        "@Entity()".to_string()
     } else {
-        return String::new();
+        format!("@Entity({{ {} }})", entity_options.join(", "))
+    }
+}
+
+/**
+Class-level `@ViewEntity` of a view, e.g. `@ViewEntity({ name: "active_users", expression: "SELECT ..." })`.
+*/
+fn view_decorator(table: &Value) -> String {
+    let mut view_options: Vec<String> = Vec::new();
+    for key in ["dbName", "schema", "database"] {
+        if let Some(value) = table[key].as_str().filter(|value| !value.is_empty()) {
+            let option = if key == "dbName" { "name" } else { key };
+            view_options.push(format!("{}: {}", option, helpers::js_string(value)));
+        }
+    }
+    if let Some(expression) = table["expression"]
+        .as_str()
+        .filter(|e| !e.trim().is_empty())
+    {
+        view_options.push(format!("expression: {}", helpers::js_text(expression)));
+    }
+
+    if view_options.is_empty() {
+        "@ViewEntity()".to_string()
+    } else {
+        format!("@ViewEntity({{ {} }})", view_options.join(", "))
+    }
+}
+
+/**
+Class-level `@Index` for a table index, e.g. `@Index("IDX_tenant_email", ["tenantId", "email"], { unique: true })`.
+Entries that aren't ids of `columns` are property names, e.g. of an inherited column, and are
+written as they are.
+*/
+fn index_decorator(index: &Value, columns: &[Value]) -> String {
+    let empty_array = Vec::new();
+    let properties: Vec<String> = index["columns"]
+        .as_array()
+        .unwrap_or(&empty_array)
+        .iter()
+        .filter_map(|id| match columns.iter().find(|col| col["id"] == *id) {
+            Some(col) => col["name"].as_str(),
+            None => id.as_str(),
+        })
+        .map(helpers::js_string)
+        .collect();
+
+    let mut arguments: Vec<String> = Vec::new();
+    if let Some(name) = index["name"].as_str().filter(|name| !name.is_empty()) {
+        arguments.push(helpers::js_string(name));
+    }
+    arguments.push(format!("[{}]", properties.join(", ")));
+
+    let mut index_options: Vec<String> = Vec::new();
+    if index["unique"].as_bool().unwrap_or(false) {
+        index_options.push("unique: true".to_string());
+    }
+    // btree, hash, gin and gist only exist in SQL DDL, typeORM has no option for them.
+    match index["type"].as_str().unwrap_or("") {
+        "fulltext" => index_options.push("fulltext: true".to_string()),
+        "spatial" => index_options.push("spatial: true".to_string()),
+        _ => {}
+    }
+    if let Some(condition) = index["where"].as_str().filter(|w| !w.is_empty()) {
+        index_options.push(format!("where: {}", helpers::js_string(condition)));
     }
-    let joined_entities = entity_codes.join("\n");
-    joined_entities
+    if !index_options.is_empty() {
+        arguments.push(format!("{{ {} }}", index_options.join(", ")));
+    }
+
+    format!("@Index({})", arguments.join(", "))
 }
 
 /**
 Convert parsed typORM syntax to nodes.
+With `sourceMap: true` in `options` the result is `{ nodes, sourceMap }`, see `source_map::ast_entry`.
 */
-pub fn convert_from_typeorm(program: &str) -> String {
+pub fn convert_from_typeorm(program: &str, options_str: &str) -> String {
     let data = helpers::parse_json(program);
+    let options = helpers::parse_json(options_str);
 
     let mut tables_vec: Vec<Value> = Vec::new();
+    let mut source_map: Vec<Value> = Vec::new();
     let empty_array = Vec::new();
 
     let body_array = data["body"].as_array().unwrap_or(&empty_array);
+    // native `type` options are read back into model data types.
+    let dialect = types::dialect(&options);
+    // `enum: Name` column options refer to these.
+    let enums = helpers::declared_enums(body_array);
+    // classes other classes extend.
+    let super_classes: Vec<&str> = body_array
+        .iter()
+        .filter_map(|statement| {
+            let class = match statement["declaration"].is_object() {
+                true => &statement["declaration"],
+                false => statement,
+            };
+            class["superClass"]["name"].as_str()
+        })
+        .collect();
 
-    if body_array.len() > 0 {
-        for node in body_array {
+    if !body_array.is_empty() {
+        for statement in body_array {
+            // `export class ...` wraps the class in an export declaration.
+            let node = if statement["type"] == "ExportNamedDeclaration" {
+                &statement["declaration"]
+            } else {
+                statement
+            };
             let node_type = node["type"].as_str().unwrap_or("").to_string();
 
-            if node_type == "ClassDeclaration".to_string() {
+            if node_type == "ClassDeclaration" {
                 // Initialize a default table.
                 let mut table_object = helpers::get_default_table();
                 let table_id = table_object["id"].as_str().unwrap_or("").to_string();
 
                 let table_name = node["id"]["name"].as_str().unwrap_or("").to_string();
                 table_object["data"]["name"] = json!(table_name);
+                source_map.push(source_map::ast_entry(
+                    "table",
+                    &table_object["id"],
+                    None,
+                    statement,
+                ));
 
                 let attributes = node["body"]["body"].as_array().unwrap_or(&empty_array);
 
                 for attribute in attributes {
+                    // methods, accessors and static blocks are not columns.
+                    if attribute["type"] != "PropertyDefinition" {
+                        continue;
+                    }
+
                     let mut column_object = helpers::get_default_column();
+                    // filled by an explicit `type` option, otherwise taken from the TS type.
+                    column_object["dataType"] = json!("");
 
-                    if let Some(decorators) = attribute.get("decorators").unwrap().as_array() {
+                    if let Some(decorators) = attribute["decorators"].as_array() {
                         let is_primary_key = column_object["primaryKey"].as_bool().unwrap_or(false);
                         let is_foreign_key = column_object["foreignKey"] != json!(null);
 
@@ -283,25 +1405,82 @@ pub fn convert_from_typeorm(program: &str) -> String {
                                 continue;
                             }
 
+                            if decorator_name == "Generated" {
+                                column_object["generated"] = arguments
+                                    .first()
+                                    .map_or(json!("increment"), helpers::expression_value);
+                                continue;
+                            }
+
+                            if decorator_name == "ViewColumn" {
+                                column_object = helpers::view_column_options_extractor(
+                                    column_object,
+                                    arguments,
+                                );
+                                continue;
+                            }
+
+                            if decorator_name == "Check" {
+                                let check = helpers::check_options_extractor(arguments);
+                                column_object["checks"].as_array_mut().unwrap().push(check);
+                                continue;
+                            }
+
+                            if SPECIAL_COLUMN_DECORATORS.contains(&decorator_name.as_str()) {
+                                column_object = helpers::basic_column_options_extractor(
+                                    column_object,
+                                    arguments,
+                                );
+                                column_object =
+                                    helpers::special_column_options(column_object, &decorator_name);
+                                continue;
+                            }
+
                             if decorator_name == "PrimaryGeneratedColumn" && !is_foreign_key {
                                 // primary column
                                 column_object["primaryKey"] = json!(true);
+                                column_object["generated"] = json!("increment");
+                                column_object["dataType"] = json!("number");
 
-                                if arguments.len() > 0 {
+                                if !arguments.is_empty() {
                                     let argument_type =
                                         arguments[0]["type"].as_str().unwrap_or("").to_string();
 
                                     if argument_type == "Literal" {
-                                        let data_type =
-                                            arguments[0]["value"].as_str().unwrap_or("");
-                                        column_object["dataType"] = json!(data_type);
+                                        let strategy =
+                                            arguments[0]["value"].as_str().unwrap_or("increment");
+                                        column_object["generated"] = json!(strategy);
+                                        if strategy == "uuid" {
+                                            column_object["dataType"] = json!("uuid");
+                                        }
                                     }
+                                    column_object = helpers::basic_column_options_extractor(
+                                        column_object,
+                                        arguments,
+                                    );
                                 } else {
                                     println!("no arguments for primary key.")
                                 }
                                 continue;
                             }
 
+                            if decorator_name == "PrimaryColumn" && !is_foreign_key {
+                                // assigned primary column, possibly part of a composite key.
+                                column_object["primaryKey"] = json!(true);
+                                if !arguments.is_empty() {
+                                    column_object = helpers::basic_column_options_extractor(
+                                        column_object,
+                                        arguments,
+                                    );
+                                }
+                                if let Some(values) =
+                                    helpers::enum_reference(arguments).and_then(|n| enums.get(n))
+                                {
+                                    column_object["enum"] = values.clone();
+                                }
+                                continue;
+                            }
+
                             let mut join_type = "";
                             match decorator_name.as_str() {
                                 "OneToOne" => join_type = "one-to-one",
@@ -311,8 +1490,18 @@ pub fn convert_from_typeorm(program: &str) -> String {
                                 _ => {}
                             }
 
-                            if join_type.len() > 0 && !is_primary_key {
-                                if arguments.len() > 0 {
+                            if decorator_name == "TreeParent" || decorator_name == "TreeChildren" {
+                                column_object["foreignKey"] =
+                                    helpers::tree_relation_options_extractor(
+                                        arguments,
+                                        &decorator_name,
+                                        &table_name,
+                                    );
+                                continue;
+                            }
+
+                            if !join_type.is_empty() && !is_primary_key {
+                                if !arguments.is_empty() {
                                     column_object["foreignKey"] =
                                         helpers::foreign_key_options_extractor(
                                             arguments, join_type,
@@ -324,7 +1513,7 @@ pub fn convert_from_typeorm(program: &str) -> String {
                             }
 
                             if decorator_name == "JoinColumn" {
-                                if arguments.len() > 0 {
+                                if !arguments.is_empty() {
                                     column_object = helpers::join_column_options_extractor(
                                         column_object,
                                         arguments,
@@ -336,7 +1525,7 @@ pub fn convert_from_typeorm(program: &str) -> String {
                             }
 
                             if decorator_name == "JoinTable" {
-                                if arguments.len() > 0 {
+                                if !arguments.is_empty() {
                                     column_object = helpers::join_table_options_extractor(
                                         column_object,
                                         arguments,
@@ -347,9 +1536,20 @@ pub fn convert_from_typeorm(program: &str) -> String {
                                 continue;
                             }
 
+                            if decorator_name == "Column"
+                                && arguments
+                                    .first()
+                                    .is_some_and(|a| a["type"] == "ArrowFunctionExpression")
+                            {
+                                // embedded class, `@Column(() => Address)`.
+                                column_object =
+                                    helpers::embedded_options_extractor(column_object, arguments);
+                                continue;
+                            }
+
                             if decorator_name == "Column" && !is_foreign_key && !is_primary_key {
                                 // basic column
-                                if arguments.len() > 0 {
+                                if !arguments.is_empty() {
                                     column_object = helpers::basic_column_options_extractor(
                                         column_object,
                                         arguments,
@@ -357,18 +1557,36 @@ pub fn convert_from_typeorm(program: &str) -> String {
                                 } else {
                                     println!("no arguments for basic column.")
                                 }
+                                if let Some(values) =
+                                    helpers::enum_reference(arguments).and_then(|n| enums.get(n))
+                                {
+                                    column_object["enum"] = values.clone();
+                                }
                             }
                         }
                     }
 
                     column_object["name"] = attribute["key"]["name"].clone();
                     column_object["table"] = json!(table_id);
+                    source_map.push(source_map::ast_entry(
+                        "column",
+                        &column_object["id"],
+                        Some(&table_object["id"]),
+                        attribute,
+                    ));
 
                     let data_type = column_object["dataType"].as_str().unwrap_or("");
-                    print!("data_type.len(): {}\n", data_type.len());
-                    if data_type.len() < 1 {
-                        column_object = json!(helpers::ts_type_extractor(column_object, attribute));
+                    if data_type.is_empty()
+                        && column_object["foreignKey"].is_null()
+                        && column_object["embedded"].is_null()
+                    {
+                        column_object = helpers::ts_type_extractor(column_object, attribute);
                     }
+                    let data_type = column_object["dataType"].as_str().unwrap_or("");
+                    column_object["dataType"] = match data_type {
+                        "" => json!("string"),
+                        native => json!(types::logical_type(native, dialect)),
+                    };
 
                     table_object["data"]["columns"]
                         .as_array_mut()
@@ -376,6 +1594,103 @@ pub fn convert_from_typeorm(program: &str) -> String {
                         .push(column_object);
                 }
 
+                // classes of columns without `@Entity` are embedded into entities.
+                let has_columns = attributes.iter().any(|attribute| {
+                    has_decorator(attribute, &COLUMN_DECORATORS)
+                        || has_decorator(attribute, &SPECIAL_COLUMN_DECORATORS)
+                });
+                if !has_decorator(node, &ENTITY_DECORATORS[..3]) && has_columns {
+                    // base classes are abstract, or at least extended.
+                    let is_base =
+                        node["abstract"] == true || super_classes.contains(&table_name.as_str());
+                    table_object["data"]["kind"] =
+                        json!(if is_base { "abstract" } else { "embeddable" });
+                }
+
+                if let Some(parent) = node["superClass"]["name"].as_str() {
+                    // `@ChildEntity` makes it single table inheritance.
+                    table_object["data"]["extends"] = json!({
+                        "table": "",
+                        "tableName": parent,
+                        "strategy": "class-table",
+                        "discriminator": null
+                    });
+                }
+
+                // class-level decorators, resolved once the columns exist.
+                let class_decorators = node["decorators"].as_array().unwrap_or(&empty_array);
+                for decorator in class_decorators {
+                    let decorator_name = decorator["expression"]["callee"]["name"]
+                        .as_str()
+                        .unwrap_or("");
+                    let arguments = decorator["expression"]["arguments"]
+                        .as_array()
+                        .unwrap_or(&empty_array);
+
+                    if decorator_name == "Entity" {
+                        table_object = helpers::entity_options_extractor(table_object, arguments);
+                        continue;
+                    }
+
+                    if decorator_name == "ViewEntity" {
+                        table_object["data"]["kind"] = json!("view");
+                        table_object = helpers::entity_options_extractor(table_object, arguments);
+                        continue;
+                    }
+
+                    if decorator_name == "ChildEntity" {
+                        if table_object["data"]["extends"].is_object() {
+                            table_object["data"]["extends"]["strategy"] = json!("single-table");
+                            table_object["data"]["extends"]["discriminator"] = arguments
+                                .first()
+                                .map_or(Value::Null, helpers::expression_value);
+                        }
+                        continue;
+                    }
+
+                    if decorator_name == "Check" {
+                        let check = helpers::check_options_extractor(arguments);
+                        table_object["data"]["checks"]
+                            .as_array_mut()
+                            .unwrap()
+                            .push(check);
+                        continue;
+                    }
+
+                    if decorator_name == "Tree" {
+                        table_object["data"]["tree"] = arguments
+                            .first()
+                            .map_or(Value::Null, helpers::expression_value);
+                        continue;
+                    }
+
+                    if decorator_name == "TableInheritance" {
+                        table_object["data"]["inheritance"] =
+                            helpers::table_inheritance_options_extractor(arguments);
+                        continue;
+                    }
+
+                    if decorator_name == "Index" {
+                        let index = helpers::index_options_extractor(
+                            arguments,
+                            &table_object["data"]["columns"],
+                        );
+                        table_object["data"]["indexes"]
+                            .as_array_mut()
+                            .unwrap()
+                            .push(index);
+                    }
+                }
+
+                let primary_keys: Vec<Value> = table_object["data"]["columns"]
+                    .as_array()
+                    .unwrap_or(&empty_array)
+                    .iter()
+                    .filter(|col| col["primaryKey"].as_bool().unwrap_or(false))
+                    .map(|col| col["id"].clone())
+                    .collect();
+                table_object["data"]["primaryKey"] = json!(primary_keys);
+
                 tables_vec.push(table_object);
             } else {
                 println!("Skipping... body is of type {}.", node_type);
@@ -387,13 +1702,29 @@ pub fn convert_from_typeorm(program: &str) -> String {
 
     let mut tables_iter = tables_vec.clone();
 
-    let mut table_idx = 0;
-    let mut column_idx = 0;
+    for (table_idx, table) in tables_vec.iter().enumerate() {
+        if let Some(parent) = table["data"]["extends"]["tableName"].as_str() {
+            // resolve the parent class, dropped when it isn't declared.
+            let extends = &mut tables_iter[table_idx]["data"]["extends"];
+            match tables_vec.iter().find(|x| x["data"]["name"] == parent) {
+                Some(parent_table) => extends["table"] = parent_table["id"].clone(),
+                None => *extends = json!(null),
+            }
+        }
 
-    for table in &tables_vec {
         let columns = table["data"]["columns"].as_array().unwrap_or(&empty_array);
 
-        for column in columns {
+        for (column_idx, column) in columns.iter().enumerate() {
+            if let Some(type_name) = column["embedded"]["tableName"].as_str() {
+                // resolve the embedded class, dropped when it isn't declared.
+                let embedded =
+                    &mut tables_iter[table_idx]["data"]["columns"][column_idx]["embedded"];
+                match tables_vec.iter().find(|x| x["data"]["name"] == type_name) {
+                    Some(embedded_table) => embedded["table"] = embedded_table["id"].clone(),
+                    None => *embedded = json!(null),
+                }
+            }
+
             if column["foreignKey"] != json!(null) {
                 if let Some(fk) = column.get("foreignKey").unwrap().as_object() {
                     let mut foreign_key = fk.clone();
@@ -416,10 +1747,14 @@ pub fn convert_from_typeorm(program: &str) -> String {
                             .as_array()
                             .unwrap_or(&empty_array);
 
-                        if let Some(target_column) = target_table_columns
-                            .iter()
-                            .find(|x| x["name"] == helpers::trim_quotes(&target_column_name))
-                        {
+                        // tree relations don't name a column, they refer to the primary key.
+                        if let Some(target_column) = target_table_columns.iter().find(|x| {
+                            if target_column_name.is_empty() {
+                                x["primaryKey"] == true
+                            } else {
+                                x["name"] == helpers::trim_quotes(&target_column_name)
+                            }
+                        }) {
                             let target_table_id =
                                 target_table["id"].as_str().unwrap_or("").to_string();
                             let target_column_id =
@@ -429,7 +1764,7 @@ pub fn convert_from_typeorm(program: &str) -> String {
                                 "table": target_table_id,
                                 "tableName": target_table_name,
                                 "column": target_column_id,
-                                "columnName": target_column_name
+                                "columnName": target_column["name"]
                             });
 
                             foreign_key["target"] = target;
@@ -453,9 +1788,10 @@ pub fn convert_from_typeorm(program: &str) -> String {
                     }
                 }
             }
-            column_idx = column_idx + 1;
         }
-        table_idx = table_idx + 1;
+    }
+    if options["sourceMap"] == true {
+        return helpers::stringify_json(json!({ "nodes": tables_iter, "sourceMap": source_map }));
     }
     helpers::stringify_json(json!(tables_iter))
 }
@@ -0,0 +1,448 @@
+use crate::enums;
+use crate::files;
+use crate::helpers::{self, class_name};
+use crate::types;
+
+use regex::Regex;
+use serde_json::Value;
+
+/**
+Convert nodes to plain TS types, without decorators: an interface per table and, for entities,
+a `Create<Name>Dto` interface and an `Update<Name>Dto` type.
+
+Create DTOs leave out generated keys and values, timestamps and the collection sides of
+relations; to-one relations are set by their id field, e.g. `authorId`. Nullable columns and
+columns with a default are optional. Update DTOs make every field of the create DTO optional.
+
+- `enumStyle: "union"`: type enum columns with string literal unions instead of TS enums.
+- `dtoStyle: "class-validator"`: DTO classes validated with class-validator decorators, for
+  NestJS, see `dto_class_code`. Embeddables get a `<Name>Dto` class for nested validation.
+*/
+pub fn convert_to_typescript(json_str: &str, options_str: &str) -> String {
+    let options = helpers::parse_json(options_str);
+    let nodes = helpers::parse_json(json_str);
+    let tables: Vec<&Value> = match nodes.as_array() {
+        Some(json_array) => json_array.iter().map(|data| &data["data"]).collect(),
+        None => return String::new(),
+    };
+
+    let union_enums = options["enumStyle"] == "union";
+    let validated = options["dtoStyle"] == "class-validator";
+    // names of the decorators and mapped types the DTO classes use, to import them.
+    let mut imported: Vec<&str> = Vec::new();
+    let mut codes: Vec<String> = enums::declarations(&tables, union_enums)
+        .into_iter()
+        .map(|(_, code)| code)
+        .collect();
+    // DTO classes are values, nested DTOs and parents are declared before their use.
+    for table in helpers::declaration_order(&tables) {
+        codes.push(interface_code(table, &tables));
+        let name = class_name(table);
+        // embeddables, base classes and views have no rows of their own to create.
+        if table["kind"].is_null() || table["kind"] == "entity" {
+            if validated {
+                let create_dto = format!("Create{}Dto", name);
+                codes.push(dto_class_code(
+                    &create_dto,
+                    table,
+                    &tables,
+                    union_enums,
+                    &mut imported,
+                ));
+                imported.push("PartialType");
+                codes.push(format!(
+                    "export class Update{}Dto extends PartialType({}) {{}}",
+                    name, create_dto
+                ));
+            } else {
+                codes.push(create_dto_code(table, &tables));
+                codes.push(format!(
+                    "export type Update{}Dto = Partial<Create{}Dto>;",
+                    name, name
+                ));
+            }
+        } else if validated && table["kind"] == "embeddable" {
+            let dto = format!("{}Dto", name);
+            codes.push(dto_class_code(
+                &dto,
+                table,
+                &tables,
+                union_enums,
+                &mut imported,
+            ));
+        }
+    }
+    let code = codes.join("\n\n");
+    if validated {
+        return with_imports(&code, &imported);
+    }
+    code
+}
+
+/**
+`code` preceded by the mapped types, class-transformer and class-validator imports of the
+`imported` names it uses.
+*/
+fn with_imports(code: &str, imported: &[&str]) -> String {
+    let mut validators: Vec<&str> = Vec::new();
+    for name in imported {
+        if !["PartialType", "Type"].contains(name) && !validators.contains(name) {
+            validators.push(name);
+        }
+    }
+    validators.sort();
+
+    let mut imports: Vec<String> = Vec::new();
+    if imported.contains(&"PartialType") {
+        imports.push(files::import(&["PartialType"], "@nestjs/mapped-types"));
+    }
+    if imported.contains(&"Type") {
+        imports.push(files::import(&["Type"], "class-transformer"));
+    }
+    if !validators.is_empty() {
+        imports.push(files::import(&validators, "class-validator"));
+    }
+    if imports.is_empty() {
+        return code.to_string();
+    }
+    format!("{}\n\n{}", imports.join("\n"), code)
+}
+
+/**
+`export interface Name { ... }` with a property per column, extending the interface of the
+parent table.
+*/
+fn interface_code(table: &Value, tables: &[&Value]) -> String {
+    let empty_array = Vec::new();
+    let name = class_name(table);
+    let mut declaration = format!("export interface {}", name);
+    if let Some(parent) = table["extends"]["tableName"]
+        .as_str()
+        .filter(|parent| !parent.is_empty())
+    {
+        declaration = format!("{} extends {}", declaration, parent);
+    }
+
+    let mut properties: Vec<String> = Vec::new();
+    for column in table["columns"].as_array().unwrap_or(&empty_array) {
+        let column_name = column["name"].as_str().unwrap_or("");
+        let mut property = Vec::new();
+        if let Some(description) = column["description"]
+            .as_str()
+            .filter(|d| !d.trim().is_empty())
+        {
+            property.push(helpers::doc_comment(description, "    "));
+        }
+        match column["foreignKey"].as_object() {
+            Some(fk) if helpers::is_collection(column) => {
+                let target = fk["target"]["tableName"].as_str().unwrap_or("");
+                property.push(format!("    {}: {}[];", column_name, target));
+            }
+            Some(fk) => {
+                let target = fk["target"]["tableName"].as_str().unwrap_or("");
+                property.push(format!(
+                    "    {}: {};",
+                    column_name,
+                    nullable(column, target)
+                ));
+                let (id_name, id_type) = helpers::relation_id(column, tables);
+                let id_type = types::ts_type(&id_type);
+                property.push(format!("    {}: {};", id_name, nullable(column, id_type)));
+            }
+            None => property.push(format!(
+                "    {}: {};",
+                column_name,
+                nullable(column, &property_type(column, name))
+            )),
+        }
+        properties.push(property.join("\n"));
+    }
+
+    let mut code = Vec::new();
+    if let Some(description) = table["description"]
+        .as_str()
+        .filter(|d| !d.trim().is_empty())
+    {
+        code.push(helpers::doc_comment(description, ""));
+    }
+    code.push(declaration_code(&declaration, &properties, "\n"));
+    code.join("\n")
+}
+
+/**
+A field of a create DTO, a column of the table or of one of its parents.
+*/
+struct DtoField<'a> {
+    name: String,
+    // data type of the column, or of the referenced column for relation ids.
+    data_type: String,
+    ts_type: String,
+    optional: bool,
+    column: &'a Value,
+}
+
+/**
+The fields a client sends to create a row of `table`, including those of the parent tables.
+*/
+fn create_dto_fields<'a>(table: &'a Value, tables: &[&'a Value]) -> Vec<DtoField<'a>> {
+    let mut fields: Vec<DtoField> = Vec::new();
+    for owner in helpers::ancestors(table, tables)
+        .iter()
+        .rev()
+        .chain([&table])
+    {
+        let Some(columns) = owner["columns"].as_array() else {
+            continue;
+        };
+        // more than one key column makes a composite primary key.
+        let is_composite_key = columns
+            .iter()
+            .filter(|col| col["primaryKey"] == true && col["foreignKey"].is_null())
+            .count()
+            > 1;
+
+        for column in columns {
+            let column_name = column["name"].as_str().unwrap_or("");
+            let is_generated = if column["primaryKey"] == true && column["foreignKey"].is_null() {
+                helpers::generation_strategy(column, is_composite_key).is_some()
+            } else {
+                column["generated"].as_str().is_some_and(|s| !s.is_empty())
+            };
+            if is_generated
+                || column["autoIncrement"] == true
+                || is_timestamp(column)
+                || helpers::is_collection(column)
+            {
+                continue;
+            }
+
+            let (name, data_type) = if column["foreignKey"].is_object() {
+                helpers::relation_id(column, tables)
+            } else {
+                (
+                    column_name.to_string(),
+                    column["dataType"].as_str().unwrap_or("string").to_string(),
+                )
+            };
+            let ts_type = if column["foreignKey"].is_object() {
+                types::ts_type(&data_type).to_string()
+            } else {
+                property_type(column, class_name(owner))
+            };
+            let has_default = !helpers::typed_default(column).is_null()
+                || helpers::default_expression(column).is_some();
+            fields.push(DtoField {
+                name,
+                data_type,
+                ts_type,
+                optional: column["nullable"] == true || has_default,
+                column,
+            });
+        }
+    }
+    fields
+}
+
+/**
+Whether the database sets the value of a date column when a row is written: its default is an
+SQL expression such as `now()`.
+*/
+fn is_timestamp(column: &Value) -> bool {
+    matches!(
+        column["dataType"].as_str(),
+        Some("date" | "dateOnly" | "timestamptz")
+    ) && helpers::default_expression(column).is_some()
+}
+
+/**
+`export interface CreateNameDto { ... }`.
+*/
+fn create_dto_code(table: &Value, tables: &[&Value]) -> String {
+    let fields: Vec<String> = create_dto_fields(table, tables)
+        .iter()
+        .map(|field| {
+            format!(
+                "    {}{}: {};",
+                field.name,
+                if field.optional { "?" } else { "" },
+                nullable(field.column, &field.ts_type)
+            )
+        })
+        .collect();
+    let declaration = format!("export interface Create{}Dto", class_name(table));
+    declaration_code(&declaration, &fields, "\n")
+}
+
+/**
+`export class Name { ... }` of the create DTO fields of `table`, each decorated with the
+validators matching its column, see `validators`. The decorators used are added to `imported`.
+*/
+fn dto_class_code(
+    name: &str,
+    table: &Value,
+    tables: &[&Value],
+    union_enums: bool,
+    imported: &mut Vec<&'static str>,
+) -> String {
+    let fields: Vec<String> = create_dto_fields(table, tables)
+        .iter()
+        .map(|field| {
+            let mut lines: Vec<String> = Vec::new();
+            for (decorator, arguments) in validators(field, union_enums) {
+                imported.push(decorator);
+                lines.push(format!("    @{}({})", decorator, arguments));
+            }
+            // JSON carries dates as ISO strings, nested objects are validated as their DTO.
+            let ts_type = match field.data_type.as_str() {
+                _ if field.column["embedded"]["tableName"].is_string() => {
+                    format!("{}Dto", field.ts_type)
+                }
+                "date" | "timestamptz" => "string".to_string(),
+                _ => field.ts_type.clone(),
+            };
+            lines.push(format!(
+                "    {}{}: {};",
+                field.name,
+                if field.optional { "?" } else { "" },
+                nullable(field.column, &ts_type)
+            ));
+            lines.join("\n")
+        })
+        .collect();
+    declaration_code(&format!("export class {}", name), &fields, "\n\n")
+}
+
+/**
+`declaration { members }`, `declaration {}` without members.
+*/
+fn declaration_code(declaration: &str, members: &[String], separator: &str) -> String {
+    if members.is_empty() {
+        return format!("{} {{}}", declaration);
+    }
+    format!("{} {{\n{}\n}}", declaration, members.join(separator))
+}
+
+/**
+`(decorator, arguments)` of the class-validator decorators of a DTO field, derived from its
+column so API validation matches the database constraints. Nullable and defaulted fields are
+`@IsOptional()`.
+*/
+fn validators(field: &DtoField, union_enums: bool) -> Vec<(&'static str, String)> {
+    let column = field.column;
+    let mut validators: Vec<(&str, String)> = Vec::new();
+    if field.optional {
+        validators.push(("IsOptional", String::new()));
+    }
+    if let Some(embedded) = column["embedded"]["tableName"].as_str() {
+        validators.push(("ValidateNested", String::new()));
+        validators.push(("Type", format!("() => {}Dto", embedded)));
+        return validators;
+    }
+    let values = column["enum"]
+        .as_array()
+        .filter(|values| !values.is_empty());
+    if let Some(values) = values.filter(|_| column["foreignKey"].is_null()) {
+        // a union type has no runtime value, the values are listed.
+        validators.push(if union_enums {
+            let values: Vec<String> = values.iter().map(helpers::js_literal).collect();
+            ("IsIn", format!("[{}]", values.join(", ")))
+        } else {
+            ("IsEnum", field.ts_type.clone())
+        });
+        return validators;
+    }
+    let Some(validator) = types::validator(&field.data_type) else {
+        return validators;
+    };
+    validators.push((validator, String::new()));
+    let checks = if column["foreignKey"].is_null() {
+        check_validators(column, field.ts_type == "number")
+    } else {
+        Vec::new()
+    };
+    // a `length(col) <= n` check replaces the length of the column.
+    let has_max_length = checks.iter().any(|(name, _)| *name == "MaxLength");
+    if field.data_type == "string" && column["foreignKey"].is_null() && !has_max_length {
+        let length = column["length"].as_u64().unwrap_or(255);
+        validators.push(("MaxLength", length.to_string()));
+    }
+    validators.extend(checks);
+    validators
+}
+
+/**
+Validators of the simple check constraints of a column: `col >= n` and `col <= n` of numbers are
+`@Min` / `@Max`, `length(col) <= n` is `@MaxLength` and `col IN (...)` is `@IsIn`. Other checks
+are only enforced by the database.
+*/
+fn check_validators(column: &Value, is_number: bool) -> Vec<(&'static str, String)> {
+    let empty_array = Vec::new();
+    let names: Vec<&str> = [&column["name"], &column["dbName"]]
+        .iter()
+        .filter_map(|name| name.as_str())
+        .collect();
+    let identifier = r#""?(\w+)"?"#;
+    let bound = Regex::new(&format!(
+        r"^\s*{}\s*(>=|<=)\s*(-?\d+(?:\.\d+)?)\s*$",
+        identifier
+    ))
+    .unwrap();
+    let length = Regex::new(&format!(
+        r"(?i)^\s*(?:char_)?length\s*\(\s*{}\s*\)\s*<=\s*(\d+)\s*$",
+        identifier
+    ))
+    .unwrap();
+    let one_of = Regex::new(&format!(r"(?i)^\s*{}\s+IN\s*\((.*)\)\s*$", identifier)).unwrap();
+
+    let mut validators = Vec::new();
+    for check in column["checks"].as_array().unwrap_or(&empty_array) {
+        let expression = check["expression"].as_str().unwrap_or("");
+        if let Some(captures) = bound.captures(expression) {
+            if is_number && names.contains(&&captures[1]) {
+                let name = if &captures[2] == ">=" { "Min" } else { "Max" };
+                validators.push((name, captures[3].to_string()));
+            }
+        } else if let Some(captures) = length.captures(expression) {
+            if names.contains(&&captures[1]) {
+                validators.push(("MaxLength", captures[2].to_string()));
+            }
+        } else if let Some(captures) = one_of.captures(expression) {
+            if names.contains(&&captures[1]) {
+                let values: Vec<String> = captures[2]
+                    .split(',')
+                    .map(|value| {
+                        let value = value.trim();
+                        match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+                            Some(text) => helpers::js_string(&text.replace("''", "'")),
+                            None => value.to_string(),
+                        }
+                    })
+                    .collect();
+                validators.push(("IsIn", format!("[{}]", values.join(", "))));
+            }
+        }
+    }
+    validators
+}
+
+/**
+TS type of a column that isn't a relation: its enum, embedded interface or data type.
+*/
+fn property_type(column: &Value, class_name: &str) -> String {
+    if let Some(enum_type) = enums::type_name(column, class_name) {
+        return enum_type;
+    }
+    if let Some(embedded) = column["embedded"]["tableName"].as_str() {
+        return embedded.to_string();
+    }
+    let data_type = column["dataType"].as_str().unwrap_or("string");
+    types::ts_type(data_type).to_string()
+}
+
+fn nullable(column: &Value, ts_type: &str) -> String {
+    if column["nullable"] == true {
+        format!("{} | null", ts_type)
+    } else {
+        ts_type.to_string()
+    }
+}
@@ -0,0 +1,194 @@
+use crate::helpers::{self, class_name};
+use crate::types;
+
+use serde_json::Value;
+
+/**
+Convert nodes to Zod schemas: a `<Name>Schema` object per table and its inferred `<Name>` type.
+
+Relations are left out of the inferred types, a table with relations also gets a
+`<Name>WithRelations` type and schema extending it with lazy references to the related
+schemas, so tables can reference each other in cycles. To-one relations are also set by their
+id field, e.g. `authorId`, which is part of the base schema.
+*/
+pub fn convert_to_zod(json_str: &str, _options_str: &str) -> String {
+    let nodes = helpers::parse_json(json_str);
+    let tables: Vec<&Value> = match nodes.as_array() {
+        Some(json_array) => json_array.iter().map(|data| &data["data"]).collect(),
+        None => return String::new(),
+    };
+
+    let mut codes: Vec<String> = vec!["import { z } from \"zod\";".to_string()];
+    for table in helpers::declaration_order(&tables) {
+        codes.push(schema_code(table, &tables));
+        if !relations(table, &tables).is_empty() {
+            codes.push(relations_schema_code(table, &tables));
+        }
+    }
+    codes.join("\n\n")
+}
+
+/**
+`export const NameSchema = z.object({ ... });` with a field per column, extending the schema of
+the parent table, followed by the inferred type.
+*/
+fn schema_code(table: &Value, tables: &[&Value]) -> String {
+    let empty_array = Vec::new();
+    let name = class_name(table);
+    let mut fields: Vec<String> = Vec::new();
+    for column in table["columns"].as_array().unwrap_or(&empty_array) {
+        if column["foreignKey"].is_object() {
+            if !helpers::is_collection(column) {
+                let (id_name, data_type) = helpers::relation_id(column, tables);
+                fields.push(format!(
+                    "    {}: {},",
+                    id_name,
+                    modifiers(column, &type_schema(&data_type, column))
+                ));
+            }
+            continue;
+        }
+        let column_name = column["name"].as_str().unwrap_or("");
+        fields.push(format!(
+            "    {}: {},",
+            column_name,
+            modifiers(column, &column_schema(column))
+        ));
+    }
+
+    let object = match table["extends"]["tableName"].as_str() {
+        Some(parent) if !parent.is_empty() => format!("{}Schema.extend", parent),
+        _ => "z.object".to_string(),
+    };
+    format!(
+        "export const {}Schema = {}({{\n{}\n}});\nexport type {} = z.infer<typeof {}Schema>;",
+        name,
+        object,
+        fields.join("\n"),
+        name,
+        name
+    )
+}
+
+/**
+`<Name>WithRelations` type and schema: the base schema extended with the relations of the
+table and its parents. The type is declared, a recursive schema can't be inferred.
+*/
+fn relations_schema_code(table: &Value, tables: &[&Value]) -> String {
+    let name = class_name(table);
+    let mut type_fields: Vec<String> = Vec::new();
+    let mut schema_fields: Vec<String> = Vec::new();
+    for column in relations(table, tables) {
+        let column_name = column["name"].as_str().unwrap_or("");
+        let target = column["foreignKey"]["target"]["tableName"]
+            .as_str()
+            .unwrap_or("");
+        let target_name = match tables.iter().find(|t| class_name(t) == target) {
+            Some(target_table) if !relations(target_table, tables).is_empty() => {
+                format!("{}WithRelations", target)
+            }
+            _ => target.to_string(),
+        };
+        // relations are only present when they are loaded.
+        if helpers::is_collection(column) {
+            type_fields.push(format!("    {}?: {}[];", column_name, target_name));
+            schema_fields.push(format!(
+                "    {}: z.lazy(() => z.array({}Schema)).optional(),",
+                column_name, target_name
+            ));
+        } else {
+            let nullable = column["nullable"] == true;
+            type_fields.push(format!(
+                "    {}?: {}{};",
+                column_name,
+                target_name,
+                if nullable { " | null" } else { "" }
+            ));
+            schema_fields.push(format!(
+                "    {}: z.lazy(() => {}Schema){}.optional(),",
+                column_name,
+                target_name,
+                if nullable { ".nullable()" } else { "" }
+            ));
+        }
+    }
+    format!(
+        "export type {name}WithRelations = {name} & {{\n{}\n}};\nexport const {name}WithRelationsSchema: z.ZodType<{name}WithRelations, z.ZodTypeDef, unknown> = {name}Schema.extend({{\n{}\n}});",
+        type_fields.join("\n"),
+        schema_fields.join("\n"),
+        name = name
+    )
+}
+
+/**
+Relation columns of `table` and its parents.
+*/
+fn relations<'a>(table: &'a Value, tables: &[&'a Value]) -> Vec<&'a Value> {
+    helpers::ancestors(table, tables)
+        .into_iter()
+        .rev()
+        .chain([table])
+        .filter_map(|owner| owner["columns"].as_array())
+        .flatten()
+        .filter(|column| column["foreignKey"].is_object())
+        .collect()
+}
+
+/**
+Schema of a column that isn't a relation: its enum values, embedded schema or data type.
+*/
+fn column_schema(column: &Value) -> String {
+    if let Some(values) = column["enum"]
+        .as_array()
+        .filter(|values| !values.is_empty())
+    {
+        let values: Vec<String> = values.iter().map(helpers::js_literal).collect();
+        return format!("z.enum([{}])", values.join(", "));
+    }
+    if let Some(embedded) = column["embedded"]["tableName"].as_str() {
+        return format!("{}Schema", embedded);
+    }
+    let data_type = column["dataType"].as_str().unwrap_or("string");
+    type_schema(data_type, column)
+}
+
+/**
+Schema of a data type, `string` columns are limited to their `length`.
+*/
+fn type_schema(data_type: &str, column: &Value) -> String {
+    if data_type == "string" && column["foreignKey"].is_null() {
+        return format!(
+            "{}.max({})",
+            types::zod_schema(data_type),
+            column["length"].as_u64().unwrap_or(255)
+        );
+    }
+    types::zod_schema(data_type).to_string()
+}
+
+/**
+`.nullable()`, `.default(value)` and `.describe(description)` of a column.
+Columns with an SQL expression default are optional, the database sets them.
+*/
+fn modifiers(column: &Value, schema: &str) -> String {
+    let mut schema = schema.to_string();
+    if column["nullable"] == true {
+        schema.push_str(".nullable()");
+    }
+    let default_value = helpers::typed_default(column);
+    if !default_value.is_null() {
+        schema.push_str(&format!(
+            ".default({})",
+            helpers::js_literal(&default_value)
+        ));
+    } else if helpers::default_expression(column).is_some() {
+        schema.push_str(".optional()");
+    }
+    if let Some(description) = column["description"]
+        .as_str()
+        .filter(|d| !d.trim().is_empty())
+    {
+        schema.push_str(&format!(".describe({})", helpers::js_string(description)));
+    }
+    schema
+}
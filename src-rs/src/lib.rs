@@ -1,36 +1,84 @@
+#[path = "utils/enums.rs"]
+mod enums;
+#[path = "utils/files.rs"]
+mod files;
 #[path = "utils/helpers.rs"]
 mod helpers;
-#[path = "compilers/mongoose.rs"]
-mod mongoose_compiler;
+#[path = "utils/inflection.rs"]
+mod inflection;
+#[path = "utils/lint.rs"]
+mod lint;
+#[path = "utils/normalize.rs"]
+mod normalize;
+#[path = "utils/source_map.rs"]
+mod source_map;
 #[path = "compilers/type_orm.rs"]
 mod type_orm_compiler;
+#[path = "utils/types.rs"]
+mod types;
+#[path = "compilers/typescript.rs"]
+mod typescript_compiler;
+#[path = "compilers/zod.rs"]
+mod zod_compiler;
 
 use wasm_bindgen::prelude::*;
 
 /**
 Convert nodes to typeORM syntax.
+`options` is an optional JSON object, e.g. `{ inflections: { cactus: "cacti" } }`.
+Pass `previousSource` and its parsed `previousProgram` to merge into previously generated code
+instead of replacing it, and `sourceMap: true` to get `{ code, sourceMap }` with the range of every
+table and column. `output: "files"` returns a `{ path: code }` map, one entity per file.
+`dialect` (`postgres` by default, `mysql`, `sqlite`, `mssql` or `mongodb`) picks the column types.
 */
 #[wasm_bindgen]
-pub fn convert_to_typeorm(json_str: &str) -> String {
-    type_orm_compiler::convert_to_typeorm(json_str)
+pub fn convert_to_typeorm(json_str: &str, options: Option<String>) -> String {
+    type_orm_compiler::convert_to_typeorm(json_str, &options.unwrap_or_default())
 }
 
 /**
 Convert parsed typORM syntax to nodes.
+`options` is an optional JSON object, `{ sourceMap: true }` returns `{ nodes, sourceMap }` mapping
+AST ranges to the created table and column ids. `dialect` reads column types back into data types.
 */
 #[wasm_bindgen]
-pub fn convert_from_typeorm(program: &str) -> String {
-    type_orm_compiler::convert_from_typeorm(program)
+pub fn convert_from_typeorm(program: &str, options: Option<String>) -> String {
+    type_orm_compiler::convert_from_typeorm(program, &options.unwrap_or_default())
 }
 
-fn main() {
-    println!("\nRunning rust library ⚙️\n");
+/**
+Convert nodes to plain TS interfaces and create / update DTO types, without decorators.
+`options` is an optional JSON object, `{ enumStyle: "union" }` types enums as string literal unions,
+`{ dtoStyle: "class-validator" }` emits NestJS DTO classes with class-validator decorators.
+*/
+#[wasm_bindgen]
+pub fn convert_to_typescript(json_str: &str, options: Option<String>) -> String {
+    typescript_compiler::convert_to_typescript(json_str, &options.unwrap_or_default())
+}
 
-    let payload = r#"
-        {"type":"Program","range":[0,472],"body":[{"type":"ClassDeclaration","abstract":false,"body":{"type":"ClassBody","range":[15,303],"body":[{"type":"PropertyDefinition","computed":false,"declare":false,"decorators":[{"type":"Decorator","expression":{"type":"CallExpression","arguments":[{"type":"Literal","raw":"\"uuid\"","value":"uuid","range":[43,49],"loc":{"end":{"column":32,"line":2},"start":{"column":26,"line":2}}}],"callee":{"type":"Identifier","decorators":[],"name":"PrimaryGeneratedColumn","optional":false,"range":[20,42],"loc":{"end":{"column":25,"line":2},"start":{"column":3,"line":2}}},"optional":false,"range":[20,50],"loc":{"end":{"column":33,"line":2},"start":{"column":3,"line":2}}},"range":[19,50],"loc":{"end":{"column":33,"line":2},"start":{"column":2,"line":2}}}],"definite":false,"key":{"type":"Identifier","decorators":[],"name":"id","optional":false,"range":[53,55],"loc":{"end":{"column":4,"line":3},"start":{"column":2,"line":3}}},"optional":false,"override":false,"readonly":false,"static":false,"typeAnnotation":{"type":"TSTypeAnnotation","loc":{"end":{"column":12,"line":3},"start":{"column":4,"line":3}},"range":[55,63],"typeAnnotation":{"type":"TSStringKeyword","range":[57,63],"loc":{"end":{"column":12,"line":3},"start":{"column":6,"line":3}}}},"value":null,"range":[19,64],"loc":{"end":{"column":13,"line":3},"start":{"column":2,"line":2}}},{"type":"PropertyDefinition","computed":false,"declare":false,"decorators":[{"type":"Decorator","expression":{"type":"CallExpression","arguments":[{"type":"ObjectExpression","properties":[{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"name","optional":false,"range":[78,82],"loc":{"end":{"column":16,"line":5},"start":{"column":12,"line":5}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"\"name\"","value":"name","range":[84,90],"loc":{"end":{"column":24,"line":5},"start":{"column":18,"line":5}}},"range":[78,90],"loc":{"end":{"column":24,"line":5},"start":{"column":12,"line":5}}},{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"length","optional":false,"range":[92,98],"loc":{"end":{"column":32,"line":5},"start":{"column":26,"line":5}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"255","value":255,"range":[100,103],"loc":{"end":{"column":37,"line":5},"start":{"column":34,"line":5}}},"range":[92,103],"loc":{"end":{"column":37,"line":5},"start":{"column":26,"line":5}}},{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"nullable","optional":false,"range":[105,113],"loc":{"end":{"column":47,"line":5},"start":{"column":39,"line":5}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"false","value":false,"range":[115,120],"loc":{"end":{"column":54,"line":5},"start":{"column":49,"line":5}}},"range":[105,120],"loc":{"end":{"column":54,"line":5},"start":{"column":39,"line":5}}},{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"unique","optional":false,"range":[122,128],"loc":{"end":{"column":62,"line":5},"start":{"column":56,"line":5}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"true","value":true,"range":[130,134],"loc":{"end":{"column":68,"line":5},"start":{"column":64,"line":5}}},"range":[122,134],"loc":{"end":{"column":68,"line":5},"start":{"column":56,"line":5}}}],"range":[76,136],"loc":{"end":{"column":70,"line":5},"start":{"column":10,"line":5}}}],"callee":{"type":"Identifier","decorators":[],"name":"Column","optional":false,"range":[69,75],"loc":{"end":{"column":9,"line":5},"start":{"column":3,"line":5}}},"optional":false,"range":[69,137],"loc":{"end":{"column":71,"line":5},"start":{"column":3,"line":5}}},"range":[68,137],"loc":{"end":{"column":71,"line":5},"start":{"column":2,"line":5}}},{"type":"Decorator","expression":{"type":"CallExpression","arguments":[],"callee":{"type":"Identifier","decorators":[],"name":"Index","optional":false,"range":[141,146],"loc":{"end":{"column":8,"line":6},"start":{"column":3,"line":6}}},"optional":false,"range":[141,148],"loc":{"end":{"column":10,"line":6},"start":{"column":3,"line":6}}},"range":[140,148],"loc":{"end":{"column":10,"line":6},"start":{"column":2,"line":6}}}],"definite":false,"key":{"type":"Identifier","decorators":[],"name":"name","optional":false,"range":[151,155],"loc":{"end":{"column":6,"line":7},"start":{"column":2,"line":7}}},"optional":false,"override":false,"readonly":false,"static":false,"typeAnnotation":{"type":"TSTypeAnnotation","loc":{"end":{"column":14,"line":7},"start":{"column":6,"line":7}},"range":[155,163],"typeAnnotation":{"type":"TSStringKeyword","range":[157,163],"loc":{"end":{"column":14,"line":7},"start":{"column":8,"line":7}}}},"value":null,"range":[68,164],"loc":{"end":{"column":15,"line":7},"start":{"column":2,"line":5}}},{"type":"PropertyDefinition","computed":false,"declare":false,"decorators":[{"type":"Decorator","expression":{"type":"CallExpression","arguments":[{"type":"ArrowFunctionExpression","async":false,"body":{"type":"Identifier","decorators":[],"name":"Contact","optional":false,"range":[185,192],"loc":{"end":{"column":26,"line":9},"start":{"column":19,"line":9}}},"expression":true,"generator":false,"id":null,"params":[],"range":[179,192],"loc":{"end":{"column":26,"line":9},"start":{"column":13,"line":9}}},{"type":"ArrowFunctionExpression","async":false,"body":{"type":"MemberExpression","computed":false,"object":{"type":"Identifier","decorators":[],"name":"contact","optional":false,"range":[207,214],"loc":{"end":{"column":48,"line":9},"start":{"column":41,"line":9}}},"optional":false,"property":{"type":"Identifier","decorators":[],"name":"id","optional":false,"range":[215,217],"loc":{"end":{"column":51,"line":9},"start":{"column":49,"line":9}}},"range":[207,217],"loc":{"end":{"column":51,"line":9},"start":{"column":41,"line":9}}},"expression":true,"generator":false,"id":null,"params":[{"type":"Identifier","decorators":[],"name":"contact","optional":false,"range":[195,202],"loc":{"end":{"column":36,"line":9},"start":{"column":29,"line":9}}}],"range":[194,217],"loc":{"end":{"column":51,"line":9},"start":{"column":28,"line":9}}},{"type":"ObjectExpression","properties":[{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"onDelete","optional":false,"range":[221,229],"loc":{"end":{"column":63,"line":9},"start":{"column":55,"line":9}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"\"SET NULL\"","value":"SET NULL","range":[231,241],"loc":{"end":{"column":75,"line":9},"start":{"column":65,"line":9}}},"range":[221,241],"loc":{"end":{"column":75,"line":9},"start":{"column":55,"line":9}}}],"range":[219,243],"loc":{"end":{"column":77,"line":9},"start":{"column":53,"line":9}}}],"callee":{"type":"Identifier","decorators":[],"name":"OneToMany","optional":false,"range":[169,178],"loc":{"end":{"column":12,"line":9},"start":{"column":3,"line":9}}},"optional":false,"range":[169,244],"loc":{"end":{"column":78,"line":9},"start":{"column":3,"line":9}}},"range":[168,244],"loc":{"end":{"column":78,"line":9},"start":{"column":2,"line":9}}},{"type":"Decorator","expression":{"type":"CallExpression","arguments":[{"type":"ObjectExpression","properties":[{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"name","optional":false,"range":[261,265],"loc":{"end":{"column":20,"line":10},"start":{"column":16,"line":10}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"\"contact_id\"","value":"contact_id","range":[267,279],"loc":{"end":{"column":34,"line":10},"start":{"column":22,"line":10}}},"range":[261,279],"loc":{"end":{"column":34,"line":10},"start":{"column":16,"line":10}}}],"range":[259,281],"loc":{"end":{"column":36,"line":10},"start":{"column":14,"line":10}}}],"callee":{"type":"Identifier","decorators":[],"name":"JoinColumn","optional":false,"range":[248,258],"loc":{"end":{"column":13,"line":10},"start":{"column":3,"line":10}}},"optional":false,"range":[248,282],"loc":{"end":{"column":37,"line":10},"start":{"column":3,"line":10}}},"range":[247,282],"loc":{"end":{"column":37,"line":10},"start":{"column":2,"line":10}}}],"definite":false,"key":{"type":"Identifier","decorators":[],"name":"contact","optional":false,"range":[285,292],"loc":{"end":{"column":9,"line":11},"start":{"column":2,"line":11}}},"optional":false,"override":false,"readonly":false,"static":false,"typeAnnotation":{"type":"TSTypeAnnotation","loc":{"end":{"column":18,"line":11},"start":{"column":9,"line":11}},"range":[292,301],"typeAnnotation":{"type":"TSTypeReference","typeName":{"type":"Identifier","decorators":[],"name":"Contact","optional":false,"range":[294,301],"loc":{"end":{"column":18,"line":11},"start":{"column":11,"line":11}}},"range":[294,301],"loc":{"end":{"column":18,"line":11},"start":{"column":11,"line":11}}}},"value":null,"range":[168,301],"loc":{"end":{"column":18,"line":11},"start":{"column":2,"line":9}}}],"loc":{"end":{"column":1,"line":12},"start":{"column":15,"line":1}}},"declare":false,"decorators":[],"id":{"type":"Identifier","decorators":[],"name":"Customer","optional":false,"range":[6,14],"loc":{"end":{"column":14,"line":1},"start":{"column":6,"line":1}}},"implements":[],"superClass":null,"range":[0,303],"loc":{"end":{"column":1,"line":12},"start":{"column":0,"line":1}}},{"type":"ClassDeclaration","abstract":false,"body":{"type":"ClassBody","range":[319,472],"body":[{"type":"PropertyDefinition","computed":false,"declare":false,"decorators":[{"type":"Decorator","expression":{"type":"CallExpression","arguments":[{"type":"Literal","raw":"\"uuid\"","value":"uuid","range":[347,353],"loc":{"end":{"column":32,"line":15},"start":{"column":26,"line":15}}}],"callee":{"type":"Identifier","decorators":[],"name":"PrimaryGeneratedColumn","optional":false,"range":[324,346],"loc":{"end":{"column":25,"line":15},"start":{"column":3,"line":15}}},"optional":false,"range":[324,354],"loc":{"end":{"column":33,"line":15},"start":{"column":3,"line":15}}},"range":[323,354],"loc":{"end":{"column":33,"line":15},"start":{"column":2,"line":15}}}],"definite":false,"key":{"type":"Identifier","decorators":[],"name":"id","optional":false,"range":[357,359],"loc":{"end":{"column":4,"line":16},"start":{"column":2,"line":16}}},"optional":false,"override":false,"readonly":false,"static":false,"typeAnnotation":{"type":"TSTypeAnnotation","loc":{"end":{"column":12,"line":16},"start":{"column":4,"line":16}},"range":[359,367],"typeAnnotation":{"type":"TSStringKeyword","range":[361,367],"loc":{"end":{"column":12,"line":16},"start":{"column":6,"line":16}}}},"value":null,"range":[323,368],"loc":{"end":{"column":13,"line":16},"start":{"column":2,"line":15}}},{"type":"PropertyDefinition","computed":false,"declare":false,"decorators":[{"type":"Decorator","expression":{"type":"CallExpression","arguments":[{"type":"ObjectExpression","properties":[{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"name","optional":false,"range":[382,386],"loc":{"end":{"column":16,"line":18},"start":{"column":12,"line":18}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"\"phone\"","value":"phone","range":[388,395],"loc":{"end":{"column":25,"line":18},"start":{"column":18,"line":18}}},"range":[382,395],"loc":{"end":{"column":25,"line":18},"start":{"column":12,"line":18}}},{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"length","optional":false,"range":[397,403],"loc":{"end":{"column":33,"line":18},"start":{"column":27,"line":18}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"255","value":255,"range":[405,408],"loc":{"end":{"column":38,"line":18},"start":{"column":35,"line":18}}},"range":[397,408],"loc":{"end":{"column":38,"line":18},"start":{"column":27,"line":18}}},{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"nullable","optional":false,"range":[410,418],"loc":{"end":{"column":48,"line":18},"start":{"column":40,"line":18}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"false","value":false,"range":[420,425],"loc":{"end":{"column":55,"line":18},"start":{"column":50,"line":18}}},"range":[410,425],"loc":{"end":{"column":55,"line":18},"start":{"column":40,"line":18}}},{"type":"Property","computed":false,"key":{"type":"Identifier","decorators":[],"name":"unique","optional":false,"range":[427,433],"loc":{"end":{"column":63,"line":18},"start":{"column":57,"line":18}}},"kind":"init","method":false,"optional":false,"shorthand":false,"value":{"type":"Literal","raw":"true","value":true,"range":[435,439],"loc":{"end":{"column":69,"line":18},"start":{"column":65,"line":18}}},"range":[427,439],"loc":{"end":{"column":69,"line":18},"start":{"column":57,"line":18}}}],"range":[380,441],"loc":{"end":{"column":71,"line":18},"start":{"column":10,"line":18}}}],"callee":{"type":"Identifier","decorators":[],"name":"Column","optional":false,"range":[373,379],"loc":{"end":{"column":9,"line":18},"start":{"column":3,"line":18}}},"optional":false,"range":[373,442],"loc":{"end":{"column":72,"line":18},"start":{"column":3,"line":18}}},"range":[372,442],"loc":{"end":{"column":72,"line":18},"start":{"column":2,"line":18}}},{"type":"Decorator","expression":{"type":"CallExpression","arguments":[],"callee":{"type":"Identifier","decorators":[],"name":"Index","optional":false,"range":[446,451],"loc":{"end":{"column":8,"line":19},"start":{"column":3,"line":19}}},"optional":false,"range":[446,453],"loc":{"end":{"column":10,"line":19},"start":{"column":3,"line":19}}},"range":[445,453],"loc":{"end":{"column":10,"line":19},"start":{"column":2,"line":19}}}],"definite":false,"key":{"type":"Identifier","decorators":[],"name":"phone","optional":false,"range":[456,461],"loc":{"end":{"column":7,"line":20},"start":{"column":2,"line":20}}},"optional":false,"override":false,"readonly":false,"static":false,"typeAnnotation":{"type":"TSTypeAnnotation","loc":{"end":{"column":15,"line":20},"start":{"column":7,"line":20}},"range":[461,469],"typeAnnotation":{"type":"TSStringKeyword","range":[463,469],"loc":{"end":{"column":15,"line":20},"start":{"column":9,"line":20}}}},"value":null,"range":[372,470],"loc":{"end":{"column":16,"line":20},"start":{"column":2,"line":18}}}],"loc":{"end":{"column":1,"line":21},"start":{"column":14,"line":14}}},"declare":false,"decorators":[],"id":{"type":"Identifier","decorators":[],"name":"Contact","optional":false,"range":[311,318],"loc":{"end":{"column":13,"line":14},"start":{"column":6,"line":14}}},"implements":[],"superClass":null,"range":[305,472],"loc":{"end":{"column":1,"line":21},"start":{"column":0,"line":14}}}],"comments":[],"sourceType":"script","tokens":[{"type":"Keyword","loc":{"end":{"column":5,"line":1},"start":{"column":0,"line":1}},"range":[0,5],"value":"class"},{"type":"Identifier","loc":{"end":{"column":14,"line":1},"start":{"column":6,"line":1}},"range":[6,14],"value":"Customer"},{"type":"Punctuator","loc":{"end":{"column":16,"line":1},"start":{"column":15,"line":1}},"range":[15,16],"value":"{"},{"type":"Punctuator","loc":{"end":{"column":3,"line":2},"start":{"column":2,"line":2}},"range":[19,20],"value":"@"},{"type":"Identifier","loc":{"end":{"column":25,"line":2},"start":{"column":3,"line":2}},"range":[20,42],"value":"PrimaryGeneratedColumn"},{"type":"Punctuator","loc":{"end":{"column":26,"line":2},"start":{"column":25,"line":2}},"range":[42,43],"value":"("},{"type":"String","loc":{"end":{"column":32,"line":2},"start":{"column":26,"line":2}},"range":[43,49],"value":"\"uuid\""},{"type":"Punctuator","loc":{"end":{"column":33,"line":2},"start":{"column":32,"line":2}},"range":[49,50],"value":")"},{"type":"Identifier","loc":{"end":{"column":4,"line":3},"start":{"column":2,"line":3}},"range":[53,55],"value":"id"},{"type":"Punctuator","loc":{"end":{"column":5,"line":3},"start":{"column":4,"line":3}},"range":[55,56],"value":":"},{"type":"Identifier","loc":{"end":{"column":12,"line":3},"start":{"column":6,"line":3}},"range":[57,63],"value":"string"},{"type":"Punctuator","loc":{"end":{"column":13,"line":3},"start":{"column":12,"line":3}},"range":[63,64],"value":";"},{"type":"Punctuator","loc":{"end":{"column":3,"line":5},"start":{"column":2,"line":5}},"range":[68,69],"value":"@"},{"type":"Identifier","loc":{"end":{"column":9,"line":5},"start":{"column":3,"line":5}},"range":[69,75],"value":"Column"},{"type":"Punctuator","loc":{"end":{"column":10,"line":5},"start":{"column":9,"line":5}},"range":[75,76],"value":"("},{"type":"Punctuator","loc":{"end":{"column":11,"line":5},"start":{"column":10,"line":5}},"range":[76,77],"value":"{"},{"type":"Identifier","loc":{"end":{"column":16,"line":5},"start":{"column":12,"line":5}},"range":[78,82],"value":"name"},{"type":"Punctuator","loc":{"end":{"column":17,"line":5},"start":{"column":16,"line":5}},"range":[82,83],"value":":"},{"type":"String","loc":{"end":{"column":24,"line":5},"start":{"column":18,"line":5}},"range":[84,90],"value":"\"name\""},{"type":"Punctuator","loc":{"end":{"column":25,"line":5},"start":{"column":24,"line":5}},"range":[90,91],"value":","},{"type":"Identifier","loc":{"end":{"column":32,"line":5},"start":{"column":26,"line":5}},"range":[92,98],"value":"length"},{"type":"Punctuator","loc":{"end":{"column":33,"line":5},"start":{"column":32,"line":5}},"range":[98,99],"value":":"},{"type":"Numeric","loc":{"end":{"column":37,"line":5},"start":{"column":34,"line":5}},"range":[100,103],"value":"255"},{"type":"Punctuator","loc":{"end":{"column":38,"line":5},"start":{"column":37,"line":5}},"range":[103,104],"value":","},{"type":"Identifier","loc":{"end":{"column":47,"line":5},"start":{"column":39,"line":5}},"range":[105,113],"value":"nullable"},{"type":"Punctuator","loc":{"end":{"column":48,"line":5},"start":{"column":47,"line":5}},"range":[113,114],"value":":"},{"type":"Boolean","loc":{"end":{"column":54,"line":5},"start":{"column":49,"line":5}},"range":[115,120],"value":"false"},{"type":"Punctuator","loc":{"end":{"column":55,"line":5},"start":{"column":54,"line":5}},"range":[120,121],"value":","},{"type":"Identifier","loc":{"end":{"column":62,"line":5},"start":{"column":56,"line":5}},"range":[122,128],"value":"unique"},{"type":"Punctuator","loc":{"end":{"column":63,"line":5},"start":{"column":62,"line":5}},"range":[128,129],"value":":"},{"type":"Boolean","loc":{"end":{"column":68,"line":5},"start":{"column":64,"line":5}},"range":[130,134],"value":"true"},{"type":"Punctuator","loc":{"end":{"column":70,"line":5},"start":{"column":69,"line":5}},"range":[135,136],"value":"}"},{"type":"Punctuator","loc":{"end":{"column":71,"line":5},"start":{"column":70,"line":5}},"range":[136,137],"value":")"},{"type":"Punctuator","loc":{"end":{"column":3,"line":6},"start":{"column":2,"line":6}},"range":[140,141],"value":"@"},{"type":"Identifier","loc":{"end":{"column":8,"line":6},"start":{"column":3,"line":6}},"range":[141,146],"value":"Index"},{"type":"Punctuator","loc":{"end":{"column":9,"line":6},"start":{"column":8,"line":6}},"range":[146,147],"value":"("},{"type":"Punctuator","loc":{"end":{"column":10,"line":6},"start":{"column":9,"line":6}},"range":[147,148],"value":")"},{"type":"Identifier","loc":{"end":{"column":6,"line":7},"start":{"column":2,"line":7}},"range":[151,155],"value":"name"},{"type":"Punctuator","loc":{"end":{"column":7,"line":7},"start":{"column":6,"line":7}},"range":[155,156],"value":":"},{"type":"Identifier","loc":{"end":{"column":14,"line":7},"start":{"column":8,"line":7}},"range":[157,163],"value":"string"},{"type":"Punctuator","loc":{"end":{"column":15,"line":7},"start":{"column":14,"line":7}},"range":[163,164],"value":";"},{"type":"Punctuator","loc":{"end":{"column":3,"line":9},"start":{"column":2,"line":9}},"range":[168,169],"value":"@"},{"type":"Identifier","loc":{"end":{"column":12,"line":9},"start":{"column":3,"line":9}},"range":[169,178],"value":"OneToMany"},{"type":"Punctuator","loc":{"end":{"column":13,"line":9},"start":{"column":12,"line":9}},"range":[178,179],"value":"("},{"type":"Punctuator","loc":{"end":{"column":14,"line":9},"start":{"column":13,"line":9}},"range":[179,180],"value":"("},{"type":"Punctuator","loc":{"end":{"column":15,"line":9},"start":{"column":14,"line":9}},"range":[180,181],"value":")"},{"type":"Punctuator","loc":{"end":{"column":18,"line":9},"start":{"column":16,"line":9}},"range":[182,184],"value":"=>"},{"type":"Identifier","loc":{"end":{"column":26,"line":9},"start":{"column":19,"line":9}},"range":[185,192],"value":"Contact"},{"type":"Punctuator","loc":{"end":{"column":27,"line":9},"start":{"column":26,"line":9}},"range":[192,193],"value":","},{"type":"Punctuator","loc":{"end":{"column":29,"line":9},"start":{"column":28,"line":9}},"range":[194,195],"value":"("},{"type":"Identifier","loc":{"end":{"column":36,"line":9},"start":{"column":29,"line":9}},"range":[195,202],"value":"contact"},{"type":"Punctuator","loc":{"end":{"column":37,"line":9},"start":{"column":36,"line":9}},"range":[202,203],"value":")"},{"type":"Punctuator","loc":{"end":{"column":40,"line":9},"start":{"column":38,"line":9}},"range":[204,206],"value":"=>"},{"type":"Identifier","loc":{"end":{"column":48,"line":9},"start":{"column":41,"line":9}},"range":[207,214],"value":"contact"},{"type":"Punctuator","loc":{"end":{"column":49,"line":9},"start":{"column":48,"line":9}},"range":[214,215],"value":"."},{"type":"Identifier","loc":{"end":{"column":51,"line":9},"start":{"column":49,"line":9}},"range":[215,217],"value":"id"},{"type":"Punctuator","loc":{"end":{"column":52,"line":9},"start":{"column":51,"line":9}},"range":[217,218],"value":","},{"type":"Punctuator","loc":{"end":{"column":54,"line":9},"start":{"column":53,"line":9}},"range":[219,220],"value":"{"},{"type":"Identifier","loc":{"end":{"column":63,"line":9},"start":{"column":55,"line":9}},"range":[221,229],"value":"onDelete"},{"type":"Punctuator","loc":{"end":{"column":64,"line":9},"start":{"column":63,"line":9}},"range":[229,230],"value":":"},{"type":"String","loc":{"end":{"column":75,"line":9},"start":{"column":65,"line":9}},"range":[231,241],"value":"\"SET NULL\""},{"type":"Punctuator","loc":{"end":{"column":77,"line":9},"start":{"column":76,"line":9}},"range":[242,243],"value":"}"},{"type":"Punctuator","loc":{"end":{"column":78,"line":9},"start":{"column":77,"line":9}},"range":[243,244],"value":")"},{"type":"Punctuator","loc":{"end":{"column":3,"line":10},"start":{"column":2,"line":10}},"range":[247,248],"value":"@"},{"type":"Identifier","loc":{"end":{"column":13,"line":10},"start":{"column":3,"line":10}},"range":[248,258],"value":"JoinColumn"},{"type":"Punctuator","loc":{"end":{"column":14,"line":10},"start":{"column":13,"line":10}},"range":[258,259],"value":"("},{"type":"Punctuator","loc":{"end":{"column":15,"line":10},"start":{"column":14,"line":10}},"range":[259,260],"value":"{"},{"type":"Identifier","loc":{"end":{"column":20,"line":10},"start":{"column":16,"line":10}},"range":[261,265],"value":"name"},{"type":"Punctuator","loc":{"end":{"column":21,"line":10},"start":{"column":20,"line":10}},"range":[265,266],"value":":"},{"type":"String","loc":{"end":{"column":34,"line":10},"start":{"column":22,"line":10}},"range":[267,279],"value":"\"contact_id\""},{"type":"Punctuator","loc":{"end":{"column":36,"line":10},"start":{"column":35,"line":10}},"range":[280,281],"value":"}"},{"type":"Punctuator","loc":{"end":{"column":37,"line":10},"start":{"column":36,"line":10}},"range":[281,282],"value":")"},{"type":"Identifier","loc":{"end":{"column":9,"line":11},"start":{"column":2,"line":11}},"range":[285,292],"value":"contact"},{"type":"Punctuator","loc":{"end":{"column":10,"line":11},"start":{"column":9,"line":11}},"range":[292,293],"value":":"},{"type":"Identifier","loc":{"end":{"column":18,"line":11},"start":{"column":11,"line":11}},"range":[294,301],"value":"Contact"},{"type":"Punctuator","loc":{"end":{"column":1,"line":12},"start":{"column":0,"line":12}},"range":[302,303],"value":"}"},{"type":"Keyword","loc":{"end":{"column":5,"line":14},"start":{"column":0,"line":14}},"range":[305,310],"value":"class"},{"type":"Identifier","loc":{"end":{"column":13,"line":14},"start":{"column":6,"line":14}},"range":[311,318],"value":"Contact"},{"type":"Punctuator","loc":{"end":{"column":15,"line":14},"start":{"column":14,"line":14}},"range":[319,320],"value":"{"},{"type":"Punctuator","loc":{"end":{"column":3,"line":15},"start":{"column":2,"line":15}},"range":[323,324],"value":"@"},{"type":"Identifier","loc":{"end":{"column":25,"line":15},"start":{"column":3,"line":15}},"range":[324,346],"value":"PrimaryGeneratedColumn"},{"type":"Punctuator","loc":{"end":{"column":26,"line":15},"start":{"column":25,"line":15}},"range":[346,347],"value":"("},{"type":"String","loc":{"end":{"column":32,"line":15},"start":{"column":26,"line":15}},"range":[347,353],"value":"\"uuid\""},{"type":"Punctuator","loc":{"end":{"column":33,"line":15},"start":{"column":32,"line":15}},"range":[353,354],"value":")"},{"type":"Identifier","loc":{"end":{"column":4,"line":16},"start":{"column":2,"line":16}},"range":[357,359],"value":"id"},{"type":"Punctuator","loc":{"end":{"column":5,"line":16},"start":{"column":4,"line":16}},"range":[359,360],"value":":"},{"type":"Identifier","loc":{"end":{"column":12,"line":16},"start":{"column":6,"line":16}},"range":[361,367],"value":"string"},{"type":"Punctuator","loc":{"end":{"column":13,"line":16},"start":{"column":12,"line":16}},"range":[367,368],"value":";"},{"type":"Punctuator","loc":{"end":{"column":3,"line":18},"start":{"column":2,"line":18}},"range":[372,373],"value":"@"},{"type":"Identifier","loc":{"end":{"column":9,"line":18},"start":{"column":3,"line":18}},"range":[373,379],"value":"Column"},{"type":"Punctuator","loc":{"end":{"column":10,"line":18},"start":{"column":9,"line":18}},"range":[379,380],"value":"("},{"type":"Punctuator","loc":{"end":{"column":11,"line":18},"start":{"column":10,"line":18}},"range":[380,381],"value":"{"},{"type":"Identifier","loc":{"end":{"column":16,"line":18},"start":{"column":12,"line":18}},"range":[382,386],"value":"name"},{"type":"Punctuator","loc":{"end":{"column":17,"line":18},"start":{"column":16,"line":18}},"range":[386,387],"value":":"},{"type":"String","loc":{"end":{"column":25,"line":18},"start":{"column":18,"line":18}},"range":[388,395],"value":"\"phone\""},{"type":"Punctuator","loc":{"end":{"column":26,"line":18},"start":{"column":25,"line":18}},"range":[395,396],"value":","},{"type":"Identifier","loc":{"end":{"column":33,"line":18},"start":{"column":27,"line":18}},"range":[397,403],"value":"length"},{"type":"Punctuator","loc":{"end":{"column":34,"line":18},"start":{"column":33,"line":18}},"range":[403,404],"value":":"},{"type":"Numeric","loc":{"end":{"column":38,"line":18},"start":{"column":35,"line":18}},"range":[405,408],"value":"255"},{"type":"Punctuator","loc":{"end":{"column":39,"line":18},"start":{"column":38,"line":18}},"range":[408,409],"value":","},{"type":"Identifier","loc":{"end":{"column":48,"line":18},"start":{"column":40,"line":18}},"range":[410,418],"value":"nullable"},{"type":"Punctuator","loc":{"end":{"column":49,"line":18},"start":{"column":48,"line":18}},"range":[418,419],"value":":"},{"type":"Boolean","loc":{"end":{"column":55,"line":18},"start":{"column":50,"line":18}},"range":[420,425],"value":"false"},{"type":"Punctuator","loc":{"end":{"column":56,"line":18},"start":{"column":55,"line":18}},"range":[425,426],"value":","},{"type":"Identifier","loc":{"end":{"column":63,"line":18},"start":{"column":57,"line":18}},"range":[427,433],"value":"unique"},{"type":"Punctuator","loc":{"end":{"column":64,"line":18},"start":{"column":63,"line":18}},"range":[433,434],"value":":"},{"type":"Boolean","loc":{"end":{"column":69,"line":18},"start":{"column":65,"line":18}},"range":[435,439],"value":"true"},{"type":"Punctuator","loc":{"end":{"column":71,"line":18},"start":{"column":70,"line":18}},"range":[440,441],"value":"}"},{"type":"Punctuator","loc":{"end":{"column":72,"line":18},"start":{"column":71,"line":18}},"range":[441,442],"value":")"},{"type":"Punctuator","loc":{"end":{"column":3,"line":19},"start":{"column":2,"line":19}},"range":[445,446],"value":"@"},{"type":"Identifier","loc":{"end":{"column":8,"line":19},"start":{"column":3,"line":19}},"range":[446,451],"value":"Index"},{"type":"Punctuator","loc":{"end":{"column":9,"line":19},"start":{"column":8,"line":19}},"range":[451,452],"value":"("},{"type":"Punctuator","loc":{"end":{"column":10,"line":19},"start":{"column":9,"line":19}},"range":[452,453],"value":")"},{"type":"Identifier","loc":{"end":{"column":7,"line":20},"start":{"column":2,"line":20}},"range":[456,461],"value":"phone"},{"type":"Punctuator","loc":{"end":{"column":8,"line":20},"start":{"column":7,"line":20}},"range":[461,462],"value":":"},{"type":"Identifier","loc":{"end":{"column":15,"line":20},"start":{"column":9,"line":20}},"range":[463,469],"value":"string"},{"type":"Punctuator","loc":{"end":{"column":16,"line":20},"start":{"column":15,"line":20}},"range":[469,470],"value":";"},{"type":"Punctuator","loc":{"end":{"column":1,"line":21},"start":{"column":0,"line":21}},"range":[471,472],"value":"}"}],"loc":{"end":{"column":1,"line":21},"start":{"column":0,"line":1}},"parent":null}
-    "#;
+/**
+Convert nodes to Zod schemas with their inferred types, relations are referenced lazily.
+`options` is an optional JSON object, reserved for generator options.
+*/
+#[wasm_bindgen]
+pub fn convert_to_zod(json_str: &str, options: Option<String>) -> String {
+    zod_compiler::convert_to_zod(json_str, &options.unwrap_or_default())
+}
 
-    let result = convert_from_typeorm(payload);
+/**
+Canonical form of nodes, two node lists describe the same schema when their normalized forms are equal.
+*/
+#[wasm_bindgen]
+pub fn normalize(json_str: &str, options: Option<String>) -> String {
+    normalize::normalize(json_str, &options.unwrap_or_default())
+}
 
-    println!("result: {}", result)
+/**
+Warnings about nodes that compile but won't work against the database, e.g. a view selecting from
+a table missing from the model, or a generation strategy the `dialect` can't create.
+Returns a JSON array of `{ level, table, column, message }`.
+*/
+#[wasm_bindgen]
+pub fn lint(json_str: &str, options: Option<String>) -> String {
+    lint::lint(json_str, &options.unwrap_or_default())
 }
@@ -0,0 +1,78 @@
+use crate::helpers::{self, class_name};
+use crate::inflection;
+
+use serde_json::Value;
+
+/**
+Name of the TS enum (or union type) of an enum column: its `enumName`, otherwise
+`<Class><Property>`, in PascalCase. `None` for columns without enum values.
+*/
+pub fn type_name(column: &Value, class_name: &str) -> Option<String> {
+    let values = column["enum"].as_array()?;
+    if values.is_empty() || !column["foreignKey"].is_null() {
+        return None;
+    }
+    let enum_name = column["enumName"].as_str().unwrap_or("");
+    if !enum_name.is_empty() {
+        return Some(inflection::to_pascal_case(enum_name));
+    }
+    let column_name = column["name"].as_str().unwrap_or("");
+    Some(format!(
+        "{}{}",
+        inflection::to_pascal_case(class_name),
+        inflection::to_pascal_case(column_name)
+    ))
+}
+
+/**
+`(name, code)` of every enum declaration the tables need, in order of first use.
+Columns sharing an `enumName` share a single declaration. `union` declares string literal
+union types instead of TS enums.
+*/
+pub fn declarations(tables: &[&Value], union: bool) -> Vec<(String, String)> {
+    let empty_array = Vec::new();
+    let mut declarations: Vec<(String, String)> = Vec::new();
+    for table in tables {
+        for column in table["columns"].as_array().unwrap_or(&empty_array) {
+            let Some(name) = type_name(column, class_name(table)) else {
+                continue;
+            };
+            if declarations.iter().any(|(declared, _)| *declared == name) {
+                continue;
+            }
+            let values: Vec<&str> = column["enum"]
+                .as_array()
+                .unwrap_or(&empty_array)
+                .iter()
+                .filter_map(Value::as_str)
+                .collect();
+            let code = if union {
+                let literals: Vec<String> = values.iter().map(|v| helpers::js_string(v)).collect();
+                format!("export type {} = {};", name, literals.join(" | "))
+            } else {
+                let members: Vec<String> = values
+                    .iter()
+                    .map(|v| format!("    {} = {},", member_name(v), helpers::js_string(v)))
+                    .collect();
+                format!("export enum {} {{\n{}\n}}", name, members.join("\n"))
+            };
+            declarations.push((name, code));
+        }
+    }
+    declarations
+}
+
+/**
+Enum member for a value, `in progress` -> `IN_PROGRESS`.
+*/
+fn member_name(value: &str) -> String {
+    let name: String = inflection::to_snake_case(value)
+        .to_uppercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    match name.chars().next() {
+        Some(c) if !c.is_numeric() => name,
+        _ => format!("_{}", name),
+    }
+}
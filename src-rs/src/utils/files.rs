@@ -0,0 +1,91 @@
+use crate::inflection;
+
+/**
+File path of the entity `class_name` from a naming `template`, e.g. `{kebab}.entity.ts` ->
+`order-item.entity.ts`. Placeholders are `{kebab}`, `{snake}`, `{camel}` and `{pascal}`,
+and `{schema}` to group entities by database schema, e.g. `{schema}/{kebab}.entity.ts`.
+A `{schema}/` directory is left out for entities without a schema.
+*/
+pub fn file_name(template: &str, class_name: &str, schema: Option<&str>) -> String {
+    let template = match schema.filter(|schema| !schema.is_empty()) {
+        Some(schema) => template.replace("{schema}", schema),
+        None => template.replace("{schema}/", "").replace("{schema}", ""),
+    };
+    template
+        .replace("{kebab}", &inflection::to_kebab_case(class_name))
+        .replace("{snake}", &inflection::to_snake_case(class_name))
+        .replace("{camel}", &inflection::to_camel_case(class_name))
+        .replace("{pascal}", &inflection::to_pascal_case(class_name))
+}
+
+/**
+`paths` with every path that is already taken, by an earlier path or a `reserved` one, numbered
+to tell the files apart: `user.entity.ts`, `user-2.entity.ts`. Paths differing only in case
+collide too, on case-insensitive file systems.
+*/
+pub fn unique_paths(paths: Vec<String>, reserved: &[&str]) -> Vec<String> {
+    let mut taken: Vec<String> = reserved.iter().map(|path| path.to_lowercase()).collect();
+    let mut unique = Vec::new();
+    for path in paths {
+        let (dir, name) = path
+            .rsplit_once('/')
+            .map_or(("", path.as_str()), |(dir, name)| {
+                (&path[..dir.len() + 1], name)
+            });
+        let (stem, extension) = name.split_once('.').unwrap_or((name, ""));
+        let mut candidate = path.clone();
+        let mut number = 2;
+        while taken.contains(&candidate.to_lowercase()) {
+            candidate = format!("{}{}-{}", dir, stem, number);
+            if !extension.is_empty() {
+                candidate = format!("{}.{}", candidate, extension);
+            }
+            number += 1;
+        }
+        taken.push(candidate.to_lowercase());
+        unique.push(candidate);
+    }
+    unique
+}
+
+/**
+Module specifier of the file `to`, imported from the file `from`.
+`models/user.entity.ts`, `post.entity.ts` -> `../post.entity`.
+*/
+pub fn relative_module(from: &str, to: &str) -> String {
+    let from_dirs: Vec<&str> = from.split('/').collect();
+    let from_dirs = &from_dirs[..from_dirs.len() - 1];
+    let to_parts: Vec<&str> = to.split('/').collect();
+
+    let common = from_dirs
+        .iter()
+        .zip(to_parts.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut parts: Vec<&str> = vec![".."; from_dirs.len() - common];
+    if parts.is_empty() {
+        parts.push(".");
+    }
+    parts.extend(&to_parts[common..]);
+
+    let module = parts.join("/");
+    module.strip_suffix(".ts").unwrap_or(&module).to_string()
+}
+
+/**
+`import { A, B } from "module";`
+*/
+pub fn import(names: &[&str], module: &str) -> String {
+    format!("import {{ {} }} from \"{}\";", names.join(", "), module)
+}
+
+/**
+`index.ts` re-exporting every file in `paths`.
+*/
+pub fn barrel(paths: &[String]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("export * from \"{}\";", relative_module("index.ts", path)))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -1,7 +1,13 @@
+use crate::inflection;
 use nanoid::nanoid;
 use serde_json::{from_str, json, to_string, Value};
 
 pub fn parse_json(json_string: &str) -> Value {
+    // valid json is left untouched, the correction below would also rewrite string values.
+    if let Ok(parsed_json) = from_str(json_string) {
+        return parsed_json;
+    }
+
     // correct lazy json strings that do not include quoted keys.
     let json_quotes = regex::Regex::new(r"(\w+)(:)").unwrap();
     let corrected_json_string = json_quotes.replace_all(json_string, r#""$1"$2"#);
@@ -14,10 +20,91 @@ pub fn stringify_json(object: Value) -> String {
     to_string(&object).unwrap().to_string()
 }
 
+/**
+Class (and interface) name of a table, its `name`, falling back to the `dbName`.
+*/
+pub fn class_name(table: &Value) -> &str {
+    let table_name = table["dbName"].as_str().unwrap_or("Entity");
+    table["name"].as_str().unwrap_or(table_name)
+}
+
+/**
+`tables` with the parents and embeddables a table extends or embeds before it, classes and
+schemas can't be used before they are declared. The order is kept otherwise.
+*/
+pub fn declaration_order<'a>(tables: &[&'a Value]) -> Vec<&'a Value> {
+    let empty_array = Vec::new();
+    let dependencies = |table: &Value| -> Vec<String> {
+        let columns = table["columns"].as_array().unwrap_or(&empty_array);
+        columns
+            .iter()
+            .filter_map(|column| column["embedded"]["tableName"].as_str())
+            .chain(table["extends"]["tableName"].as_str())
+            .map(String::from)
+            .collect()
+    };
+    let mut ordered: Vec<&Value> = Vec::new();
+    let mut remaining: Vec<&Value> = tables.to_vec();
+    while !remaining.is_empty() {
+        let declared = |table: &&Value| {
+            dependencies(table).iter().all(|name| {
+                ordered.iter().any(|t| class_name(t) == name)
+                    || !remaining.iter().any(|t| class_name(t) == name)
+            })
+        };
+        // a dependency cycle can't be ordered, its tables are kept as they are.
+        let idx = remaining.iter().position(declared).unwrap_or(0);
+        ordered.push(remaining.remove(idx));
+    }
+    ordered
+}
+
 pub fn trim_quotes(s: &str) -> &str {
     s.trim_matches(&['\'', '"', '\"'][..])
 }
 
+/**
+Double-quoted TS string literal, escaping quotes, backslashes and line breaks.
+*/
+pub fn js_string(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+/**
+TS string literal of a possibly multi-line text, e.g. SQL. Texts spanning lines are written as
+template literals to keep their line breaks readable.
+*/
+pub fn js_text(s: &str) -> String {
+    if !s.contains('\n') {
+        return js_string(s);
+    }
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${");
+    format!("`{}`", escaped)
+}
+
+/**
+Byte offset in `source` of a UTF-16 code unit offset, as used by ESTree ranges.
+*/
+pub fn byte_offset(source: &str, utf16_offset: usize) -> usize {
+    let mut units = 0;
+    for (idx, c) in source.char_indices() {
+        if units >= utf16_offset {
+            return idx;
+        }
+        units += c.len_utf16();
+    }
+    source.len()
+}
+
 pub fn get_default_table() -> Value {
     let id = nanoid!();
     let table_object = json!({
@@ -27,11 +114,22 @@ pub fn get_default_table() -> Value {
             "id": id,
             "name": "",
             "dbName": "",
-            "primaryKey": "",
+            "kind": "entity",
+            "extends": null,
+            "inheritance": null,
+            "tree": null,
+            "expression": null,
+            "primaryKey": [],
             "description": "",
             "timestamps": true,
             "engine": "InnoDB",
+            "schema": null,
+            "database": null,
+            "synchronize": true,
+            "orderBy": [],
             "columns": [],
+            "indexes": [],
+            "checks": [],
             "joins": []
         }
     });
@@ -45,10 +143,13 @@ pub fn get_default_column() -> Value {
         "dbName": "",
         "dataType": "string",
         "primaryKey": false,
+        "generated": null,
+        "identityGeneration": null,
         "index": false,
         "unique": false,
         "nullable": false,
         "defaultValue": null,
+        "defaultKind": "literal",
         "length": 255,
         "precision": null,
         "scale": null,
@@ -56,6 +157,8 @@ pub fn get_default_column() -> Value {
         "description": "",
         "autoIncrement": false,
         "foreignKey": null,
+        "embedded": null,
+        "checks": [],
         "select": true,
         "zerofill": false,
         "enum": null,
@@ -66,7 +169,238 @@ pub fn get_default_column() -> Value {
     column_object
 }
 
-pub fn foreign_key_options_extractor(arguments: &Vec<Value>, key_type: &str) -> Value {
+// data types whose default values are written as numbers / booleans.
+const NUMERIC_TYPES: [&str; 7] = [
+    "number", "float", "int", "integer", "smallint", "double", "real",
+];
+const BOOLEAN_TYPES: [&str; 2] = ["boolean", "bool"];
+
+/**
+Literal default value of a column, typed after its data type: the editor stores `"5"` and
+`"true"` for numeric and boolean columns. `Null` without a default or with an SQL expression default.
+*/
+pub fn typed_default(column: &Value) -> Value {
+    if default_expression(column).is_some() {
+        return Value::Null;
+    }
+    let data_type = column["dataType"].as_str().unwrap_or("");
+    match &column["defaultValue"] {
+        Value::String(value) if value.is_empty() => Value::Null,
+        Value::String(value) if NUMERIC_TYPES.contains(&data_type) => {
+            let trimmed = value.trim();
+            if let Ok(number) = trimmed.parse::<i64>() {
+                json!(number)
+            } else if let Some(number) = trimmed.parse::<f64>().ok().filter(|n| n.is_finite()) {
+                json!(number)
+            } else {
+                json!(value)
+            }
+        }
+        Value::String(value) if BOOLEAN_TYPES.contains(&data_type) => match value.trim() {
+            "true" => json!(true),
+            "false" => json!(false),
+            _ => json!(value),
+        },
+        value => value.clone(),
+    }
+}
+
+/**
+SQL expression default of a column (`defaultKind: "expression"`), e.g. `now()`.
+*/
+pub fn default_expression(column: &Value) -> Option<String> {
+    if column["defaultKind"] != "expression" {
+        return None;
+    }
+    match &column["defaultValue"] {
+        Value::String(value) if value.is_empty() => None,
+        Value::String(value) => Some(value.clone()),
+        Value::Null => None,
+        value => Some(value.to_string()),
+    }
+}
+
+/**
+JSDoc block for a description, indented by `indent`. One line descriptions stay on one line.
+*/
+pub fn doc_comment(description: &str, indent: &str) -> String {
+    let description = description.trim().replace("*/", "*\\/");
+    let lines: Vec<&str> = description.lines().collect();
+    if lines.len() <= 1 {
+        return format!("{}/** {} */", indent, description);
+    }
+    let mut doc = vec![format!("{}/**", indent)];
+    for line in lines {
+        doc.push(format!("{} * {}", indent, line).trim_end().to_string());
+    }
+    doc.push(format!("{} */", indent));
+    doc.join("\n")
+}
+
+/**
+TS literal of a JSON value, e.g. `"text"`, `5`, `true`.
+*/
+pub fn js_literal(value: &Value) -> String {
+    match value {
+        Value::String(value) => js_string(value),
+        value => value.to_string(),
+    }
+}
+
+/**
+Key generation strategy of a primary column, `None` when the key is assigned by the application.
+Columns saved before `generated` existed fall back to `uuid`/`increment` unless they are
+part of a composite key.
+*/
+pub fn generation_strategy(column: &Value, is_composite_key: bool) -> Option<String> {
+    match column.get("generated") {
+        Some(strategy) => strategy.as_str().map(String::from),
+        None if is_composite_key => None,
+        None if column["dataType"] == "uuid" => Some("uuid".to_string()),
+        None => Some("increment".to_string()),
+    }
+}
+
+/**
+Whether a relation column is the collection side, one-to-many or many-to-many.
+*/
+pub fn is_collection(column: &Value) -> bool {
+    column["foreignKey"]["type"]
+        .as_str()
+        .is_some_and(|join_type| join_type.ends_with("-many"))
+}
+
+/**
+Name and data type of the id field of a to-one relation, the relation name followed by the
+referenced column, e.g. `authorId` of a `number`.
+*/
+pub fn relation_id(column: &Value, tables: &[&Value]) -> (String, String) {
+    let empty_array = Vec::new();
+    let target = &column["foreignKey"]["target"];
+    let target_table = target["tableName"].as_str().unwrap_or("");
+    let target_column = target["columnName"]
+        .as_str()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("id");
+    let data_type = tables
+        .iter()
+        .find(|table| class_name(table) == target_table)
+        .and_then(|table| {
+            table["columns"]
+                .as_array()
+                .unwrap_or(&empty_array)
+                .iter()
+                .find(|col| col["name"] == target_column)
+        })
+        .and_then(|col| col["dataType"].as_str())
+        .unwrap_or("number");
+    (
+        format!(
+            "{}{}",
+            column["name"].as_str().unwrap_or(""),
+            inflection::to_pascal_case(target_column)
+        ),
+        data_type.to_string(),
+    )
+}
+
+/**
+Parent tables of `table`, nearest first. Stops at unknown parents and inheritance cycles.
+*/
+pub fn ancestors<'a>(table: &Value, tables: &[&'a Value]) -> Vec<&'a Value> {
+    let mut ancestors: Vec<&Value> = Vec::new();
+    let mut parent_name = table["extends"]["tableName"].as_str();
+    while let Some(parent) = parent_name
+        .and_then(|name| tables.iter().find(|t| class_name(t) == name))
+        .filter(|parent| {
+            !std::ptr::eq(table, **parent) && !ancestors.iter().any(|a| std::ptr::eq(*a, **parent))
+        })
+    {
+        ancestors.push(parent);
+        parent_name = parent["extends"]["tableName"].as_str();
+    }
+    ancestors
+}
+
+/**
+Default many-to-many join table name, `<table>_<targets>`, e.g. `post_categories`.
+*/
+pub fn default_join_table_name(
+    table_name: &str,
+    target_table: &str,
+    inflector: &inflection::Inflector,
+) -> String {
+    format!(
+        "{}_{}",
+        inflection::to_snake_case(table_name),
+        inflection::to_snake_case(&inflector.pluralize(target_table))
+    )
+}
+
+/**
+Default inverse property of a relation, on the target of `class_name`'s relation: the plural
+`posts` of the `Post` many-to-one / many-to-many side, the singular `post` of its one-to-many /
+one-to-one side.
+*/
+pub fn default_inverse_name(
+    class_name: &str,
+    join_type: &str,
+    inflector: &inflection::Inflector,
+) -> String {
+    let inverse = match join_type {
+        "many-to-one" | "many-to-many" => inflector.pluralize(class_name),
+        _ => inflector.singularize(class_name),
+    };
+    inflection::to_camel_case(&inverse)
+}
+
+/**
+Plain JSON value of a literal expression: literals, arrays, objects and negative numbers.
+*/
+pub fn expression_value(expression: &Value) -> Value {
+    let empty_array = Vec::new();
+    match expression["type"].as_str().unwrap_or("") {
+        "Literal" => expression["value"].clone(),
+        "ArrayExpression" => expression["elements"]
+            .as_array()
+            .unwrap_or(&empty_array)
+            .iter()
+            .map(expression_value)
+            .collect(),
+        "ObjectExpression" => {
+            let mut object = json!({});
+            for property in expression["properties"].as_array().unwrap_or(&empty_array) {
+                let key = property["key"]["name"]
+                    .as_str()
+                    .or(property["key"]["value"].as_str())
+                    .unwrap_or("");
+                object[key] = expression_value(&property["value"]);
+            }
+            object
+        }
+        // template literals without substitutions, e.g. multi-line SQL.
+        "TemplateLiteral"
+            if expression["expressions"]
+                .as_array()
+                .is_none_or(Vec::is_empty) =>
+        {
+            expression["quasis"][0]["value"]["cooked"].clone()
+        }
+        "UnaryExpression" if expression["operator"] == "-" => expression["argument"]["value"]
+            .as_f64()
+            .map(|number| {
+                if number.fract() == 0.0 {
+                    json!(-(number as i64))
+                } else {
+                    json!(-number)
+                }
+            })
+            .unwrap_or(Value::Null),
+        _ => Value::Null,
+    }
+}
+
+pub fn foreign_key_options_extractor(arguments: &[Value], key_type: &str) -> Value {
     let mut foreign_key = json!({
         "target": null,
         "onDelete": "CASCADE",
@@ -75,52 +409,42 @@ pub fn foreign_key_options_extractor(arguments: &Vec<Value>, key_type: &str) ->
         "source": null,
         "type": key_type
     });
-    let mut i = 0;
 
     let mut target_table = String::new();
     let mut target_column = String::new();
 
     let empty_array = Vec::new();
-    for argument in arguments {
-        let mut argument_type = argument["type"].to_string();
-        argument_type = trim_quotes(&argument_type).to_string();
+    for (i, argument) in arguments.iter().enumerate() {
+        let argument_type = argument["type"].as_str().unwrap_or("");
 
         if i == 0 && argument_type == "ArrowFunctionExpression" {
             // first argument, always a function that returns the target table.
-            target_table = argument["body"].get("name").unwrap().to_string();
-            target_table = trim_quotes(&target_table).to_string();
+            target_table = argument["body"]["name"].as_str().unwrap_or("").to_string();
         }
 
         if i == 1 && argument_type == "ArrowFunctionExpression" {
-            // second argument, always a function that defines the target column.
-            target_column = argument["body"]["property"]
-                .get("name")
-                .unwrap()
+            // second argument, the inverse side of the relation.
+            target_column = argument["body"]["property"]["name"]
+                .as_str()
+                .unwrap_or("")
                 .to_string();
-            target_column = trim_quotes(&target_column).to_string();
         }
 
-        if i == 2 && argument_type == "ObjectExpression" {
-            // third argument, always a join options object
+        if i > 0 && argument_type == "ObjectExpression" {
+            // last argument, always a join options object
             let join_options = argument["properties"].as_array().unwrap_or(&empty_array);
-            if join_options.len() > 0 {
-                for option in join_options {
-                    if let Some(key_name) = option["key"].get("name").unwrap().as_str() {
-                        let key = trim_quotes(key_name);
-                        let mut value = option["value"]["value"].to_string();
-                        value = trim_quotes(&value).to_string();
-
-                        match key {
-                            "onDelete" => foreign_key["onDelete"] = json!(value),
-                            "onUpdate" => foreign_key["onDelete"] = json!(value),
-                            _ => {}
-                        }
+            for option in join_options {
+                if let Some(key_name) = option["key"]["name"].as_str() {
+                    let value = expression_value(&option["value"]);
+
+                    match key_name {
+                        "onDelete" => foreign_key["onDelete"] = value,
+                        "onUpdate" => foreign_key["onUpdate"] = value,
+                        _ => {}
                     }
                 }
             }
         }
-
-        i = i + 1;
     }
 
     foreign_key["target"] = json!({
@@ -131,45 +455,156 @@ pub fn foreign_key_options_extractor(arguments: &Vec<Value>, key_type: &str) ->
     foreign_key
 }
 
-pub fn basic_column_options_extractor(mut column_object: Value, arguments: &Vec<Value>) -> Value {
+/**
+Read `@Column(() => Type, { prefix })`, a property embedding the columns of another class.
+The embedded table is resolved by name once every class is parsed.
+*/
+pub fn embedded_options_extractor(mut column_object: Value, arguments: &[Value]) -> Value {
+    let empty_array = Vec::new();
+    let type_name = arguments[0]["body"]["name"].as_str().unwrap_or("");
+    let prefix = arguments
+        .get(1)
+        .and_then(|options| options["properties"].as_array())
+        .unwrap_or(&empty_array)
+        .iter()
+        .find(|option| option["key"]["name"] == "prefix")
+        .map(|option| expression_value(&option["value"]))
+        .unwrap_or(Value::Null);
+
+    column_object["embedded"] = json!({
+        "table": "",
+        "tableName": type_name,
+        "prefix": prefix
+    });
+    column_object
+}
+
+/**
+Read `@TableInheritance({ column: { type, name } })` into the discriminator column of a
+single table inheritance root.
+*/
+pub fn table_inheritance_options_extractor(arguments: &[Value]) -> Value {
+    let options = arguments.first().map_or(Value::Null, expression_value);
+    let column = &options["column"];
+    json!({
+        "column": column["name"].as_str().unwrap_or("type"),
+        "type": column["type"].as_str().unwrap_or("varchar")
+    })
+}
+
+/**
+Values of the TS enums and string literal union types declared in a program body, by name.
+*/
+pub fn declared_enums(body: &[Value]) -> serde_json::Map<String, Value> {
+    let empty_array = Vec::new();
+    let mut enums = serde_json::Map::new();
+    for statement in body {
+        let declaration = if statement["type"] == "ExportNamedDeclaration" {
+            &statement["declaration"]
+        } else {
+            statement
+        };
+        let Some(name) = declaration["id"]["name"].as_str() else {
+            continue;
+        };
+
+        if declaration["type"] == "TSEnumDeclaration" {
+            // newer parsers nest the members in a body.
+            let members = declaration["members"]
+                .as_array()
+                .or(declaration["body"]["members"].as_array())
+                .unwrap_or(&empty_array);
+            let values: Vec<Value> = members
+                .iter()
+                .map(|member| match &member["initializer"]["value"] {
+                    Value::Null => member["id"]["name"].clone(),
+                    value => value.clone(),
+                })
+                .collect();
+            enums.insert(name.to_string(), json!(values));
+        }
+
+        if declaration["type"] == "TSTypeAliasDeclaration" {
+            let annotation = &declaration["typeAnnotation"];
+            let types = match annotation["types"].as_array() {
+                Some(types) => types.iter().collect(),
+                None => vec![annotation],
+            };
+            if types.iter().all(|t| t["type"] == "TSLiteralType") {
+                let values: Vec<Value> = types
+                    .iter()
+                    .map(|t| t["literal"]["value"].clone())
+                    .collect();
+                enums.insert(name.to_string(), json!(values));
+            }
+        }
+    }
+    enums
+}
+
+/**
+Name of the TS enum referenced by an `enum: Name` column option.
+*/
+pub fn enum_reference(arguments: &[Value]) -> Option<&str> {
+    arguments
+        .iter()
+        .filter_map(|argument| argument["properties"].as_array())
+        .flatten()
+        .find(|option| option["key"]["name"] == "enum")
+        .and_then(|option| option["value"]["name"].as_str())
+}
+
+pub fn basic_column_options_extractor(mut column_object: Value, arguments: &[Value]) -> Value {
     let empty_array = Vec::new();
     for argument in arguments {
         let argument_type = argument["type"].as_str().unwrap_or("");
         if argument_type == "ObjectExpression" {
             let column_options = argument["properties"].as_array().unwrap_or(&empty_array);
             for option in column_options {
-                if let Some(key_name) = option["key"].get("name").unwrap().as_str() {
+                if let Some(key_name) = option["key"]["name"].as_str() {
                     let key = trim_quotes(key_name);
-
-                    let mut value = option["value"]["value"].to_string();
-                    value = trim_quotes(&value).to_string();
+                    let value = expression_value(&option["value"]);
 
                     match key {
-                        "name" => column_object["dbName"] = json!(value),
-                        "type" => column_object["dataType"] = json!(value),
-                        "index" => column_object["index"] = json!(value),
-                        "unique" => column_object["unique"] = json!(value),
-                        "nullable" => column_object["nullable"] = json!(value),
-                        "default" => column_object["defaultValue"] = json!(value),
-                        "length" => column_object["length"] = json!(value),
-                        "precision" => column_object["precision"] = json!(value),
-                        "scale" => column_object["scale"] = json!(value),
-                        "collation" => column_object["collation"] = json!(value),
-                        "autoIncrement" => column_object["autoIncrement"] = json!(value),
-                        "select" => column_object["select"] = json!(value),
-                        "zerofill" => column_object["zerofill"] = json!(value),
+                        "name" => column_object["dbName"] = value,
+                        "type" => column_object["dataType"] = value,
+                        "index" => column_object["index"] = value,
+                        "unique" => column_object["unique"] = value,
+                        "nullable" => column_object["nullable"] = value,
+                        "default" => {
+                            // `default: () => "now()"` is an SQL expression.
+                            if option["value"]["type"] == "ArrowFunctionExpression" {
+                                column_object["defaultValue"] =
+                                    expression_value(&option["value"]["body"]);
+                                column_object["defaultKind"] = json!("expression");
+                            } else {
+                                column_object["defaultValue"] = value;
+                                column_object["defaultKind"] = json!("literal");
+                            }
+                        }
+                        "length" => column_object["length"] = value,
+                        "precision" => column_object["precision"] = value,
+                        "scale" => column_object["scale"] = value,
+                        "collation" => column_object["collation"] = value,
+                        "autoIncrement" => column_object["autoIncrement"] = value,
+                        "generatedIdentity" => column_object["identityGeneration"] = value,
+                        "select" => column_object["select"] = value,
+                        "zerofill" => column_object["zerofill"] = value,
                         "enum" => {
-                            column_object["enum"] = value
-                                .clone()
-                                .to_string()
-                                .split(",")
-                                .map(|s| Value::String(s.to_string())) // Convert each item to Value::String
-                                .collect()
+                            column_object["enum"] = match value.as_str() {
+                                // older output wrote the values as one comma separated string.
+                                Some(values) => values
+                                    .split(',')
+                                    .map(|s| Value::String(trim_quotes(s.trim()).to_string()))
+                                    .collect(),
+                                None => value,
+                            }
                         }
-                        "enumName" => column_object["enumName"] = json!(value),
-                        "hstoreType" => column_object["hstoreType"] = json!(value),
-                        "unqiue" => column_object["unique"] = json!(value),
-                        "array" => column_object["array"] = json!(value),
+                        "enumName" => column_object["enumName"] = value,
+                        "comment" => column_object["description"] = value,
+                        "hstoreType" => column_object["hstoreType"] = value,
+                        "unqiue" => column_object["unique"] = value,
+                        "array" => column_object["array"] = value,
                         _ => {} // do nothing
                     }
                 }
@@ -179,23 +614,76 @@ pub fn basic_column_options_extractor(mut column_object: Value, arguments: &Vec<
     column_object
 }
 
-pub fn join_column_options_extractor(mut column_object: Value, arguments: &Vec<Value>) -> Value {
+/**
+Read `@TreeParent({ onDelete })` / `@TreeChildren()` of the tree entity `class_name` into a
+self-referencing foreign key. The target column is left empty, it is the primary key.
+*/
+pub fn tree_relation_options_extractor(
+    arguments: &[Value],
+    decorator_name: &str,
+    class_name: &str,
+) -> Value {
+    let key_type = if decorator_name == "TreeParent" {
+        "many-to-one"
+    } else {
+        "one-to-many"
+    };
+    let mut foreign_key = foreign_key_options_extractor(&[], key_type);
+    foreign_key["target"] = json!({ "table": class_name, "column": "" });
+
+    let options = arguments.first().map_or(Value::Null, expression_value);
+    if let Some(on_delete) = options.get("onDelete") {
+        foreign_key["onDelete"] = on_delete.clone();
+    }
+    foreign_key
+}
+
+/**
+What a column typeORM sets implies, e.g. `@CreateDateColumn()` defaults to the current time and
+`@DeleteDateColumn()` is null until the row is soft deleted. Options written out are kept.
+*/
+pub fn special_column_options(mut column_object: Value, decorator_name: &str) -> Value {
+    match decorator_name {
+        "CreateDateColumn" | "UpdateDateColumn" if column_object["defaultValue"].is_null() => {
+            column_object["defaultValue"] = json!("now()");
+            column_object["defaultKind"] = json!("expression");
+        }
+        "DeleteDateColumn" => column_object["nullable"] = json!(true),
+        "ObjectIdColumn" => {
+            column_object["primaryKey"] = json!(true);
+            column_object["dataType"] = json!("objectId");
+        }
+        _ => {}
+    }
+    column_object
+}
+
+/**
+Read `@ViewColumn({ name })`, the column a view property maps.
+*/
+pub fn view_column_options_extractor(mut column_object: Value, arguments: &[Value]) -> Value {
+    let options = arguments.first().map_or(Value::Null, expression_value);
+    if let Some(name) = options["name"].as_str() {
+        column_object["dbName"] = json!(name);
+    }
+    column_object
+}
+
+pub fn join_column_options_extractor(mut column_object: Value, arguments: &[Value]) -> Value {
     let empty_array = Vec::new();
     for argument in arguments {
         let argument_type = argument["type"].as_str().unwrap_or("");
         if argument_type == "ObjectExpression" {
             let column_options = argument["properties"].as_array().unwrap_or(&empty_array);
             for option in column_options {
-                if let Some(key_name) = option["key"].get("name").unwrap().as_str() {
+                if let Some(key_name) = option["key"]["name"].as_str() {
                     let key = trim_quotes(key_name);
-
-                    let mut value = option["value"]["value"].to_string();
-                    value = trim_quotes(&value).to_string();
+                    let value = expression_value(&option["value"]);
 
                     match key {
-                        "name" => column_object["dbName"] = json!(value),
+                        "name" => column_object["dbName"] = value,
                         "referencedColumnName" => {
-                            column_object["foreignKey"]["target"]["column"] = json!(value);
+                            column_object["foreignKey"]["target"]["column"] = value;
                         }
                         _ => {} // do nothing
                     }
@@ -206,30 +694,26 @@ pub fn join_column_options_extractor(mut column_object: Value, arguments: &Vec<V
     column_object
 }
 
-pub fn join_table_options_extractor(mut column_object: Value, arguments: &Vec<Value>) -> Value {
+pub fn join_table_options_extractor(mut column_object: Value, arguments: &[Value]) -> Value {
     let empty_array = Vec::new();
     for argument in arguments {
         let argument_type = argument["type"].as_str().unwrap_or("");
         if argument_type == "ObjectExpression" {
             let column_options = argument["properties"].as_array().unwrap_or(&empty_array);
             for option in column_options {
-                if let Some(key_name) = option["key"].get("name").unwrap().as_str() {
+                if let Some(key_name) = option["key"]["name"].as_str() {
                     let key = trim_quotes(key_name);
+                    let value = expression_value(&option["value"]);
 
                     match key {
                         "name" => {
-                            let value = option["value"]["value"].as_str().unwrap_or("");
-                            column_object["foreignKey"]["through"] = json!(value);
+                            column_object["foreignKey"]["through"] = value;
                         }
-                        "joinColumn" => {
-                            if let Some(value) = option["value"].get("value").unwrap().as_object() {
-                                column_object["foreignKey"]["joinColumn"] = json!(value);
-                            }
+                        "joinColumn" if value.is_object() => {
+                            column_object["foreignKey"]["joinColumn"] = value;
                         }
-                        "inverseJoinColumn" => {
-                            if let Some(value) = option["value"].get("value").unwrap().as_object() {
-                                column_object["foreignKey"]["inverseColumn"] = json!(value);
-                            }
+                        "inverseJoinColumn" if value.is_object() => {
+                            column_object["foreignKey"]["inverseColumn"] = value;
                         }
                         _ => {} // do nothing
                     }
@@ -246,20 +730,22 @@ pub fn ts_type_extractor(mut column_object: Value, attribute: &Value) -> Value {
         .unwrap_or(&json!(""))
         .as_object()
     {
-        column_object["dataType"] = type_name["name"].clone();
+        column_object["dataType"] = match type_name["name"].as_str() {
+            Some("Date") => json!("date"),
+            Some("Buffer") => json!("binary"),
+            _ => type_name["name"].clone(),
+        };
         column_object
     } else {
-        if let Some(ts_type) = attribute["typeAnnotation"]["typeAnnotation"]
-            .get("type")
-            .unwrap()
-            .as_str()
-        {
+        if let Some(ts_type) = attribute["typeAnnotation"]["typeAnnotation"]["type"].as_str() {
             match ts_type {
                 "TSStringKeyword" => column_object["dataType"] = json!("string"),
                 "TSNumberKeyword" => column_object["dataType"] = json!("number"),
                 "TSBooleanKeyword" => column_object["dataType"] = json!("boolean"),
+                "TSBigIntKeyword" => column_object["dataType"] = json!("bigint"),
                 "TSArrayType" => {
                     let element_type = attribute["typeAnnotation"]["typeAnnotation"]["elementType"]
+                        ["type"]
                         .as_str()
                         .unwrap_or("");
                     match element_type {
@@ -276,3 +762,125 @@ pub fn ts_type_extractor(mut column_object: Value, attribute: &Value) -> Value {
         column_object
     }
 }
+
+/**
+Read a class-level `@Entity(name)` or `@Entity({ ...options })` into the table.
+`orderBy` property names are resolved to the ids of the already parsed columns.
+*/
+pub fn entity_options_extractor(mut table_object: Value, arguments: &[Value]) -> Value {
+    let empty_array = Vec::new();
+    for argument in arguments {
+        let argument_type = argument["type"].as_str().unwrap_or("");
+        if argument_type == "Literal" {
+            table_object["data"]["dbName"] = argument["value"].clone();
+        }
+        if argument_type == "ObjectExpression" {
+            let entity_options = argument["properties"].as_array().unwrap_or(&empty_array);
+            for option in entity_options {
+                let key = option["key"]["name"].as_str().unwrap_or("");
+                let value = option["value"]["value"].clone();
+                match key {
+                    "name" => table_object["data"]["dbName"] = value,
+                    "schema" => table_object["data"]["schema"] = value,
+                    "database" => table_object["data"]["database"] = value,
+                    "engine" => table_object["data"]["engine"] = value,
+                    "synchronize" => table_object["data"]["synchronize"] = value,
+                    "comment" => table_object["data"]["description"] = value,
+                    "expression" => {
+                        table_object["data"]["expression"] = expression_value(&option["value"])
+                    }
+                    "orderBy" => {
+                        let orders: Vec<Value> = option["value"]["properties"]
+                            .as_array()
+                            .unwrap_or(&empty_array)
+                            .iter()
+                            .filter_map(|order| {
+                                let name = order["key"]["name"].as_str()?;
+                                let column = table_object["data"]["columns"]
+                                    .as_array()?
+                                    .iter()
+                                    .find(|col| col["name"] == name)?;
+                                Some(json!({
+                                    "column": column["id"],
+                                    "order": order["value"]["value"]
+                                }))
+                            })
+                            .collect();
+                        table_object["data"]["orderBy"] = json!(orders);
+                    }
+                    _ => {} // do nothing
+                }
+            }
+        }
+    }
+    table_object
+}
+
+/**
+Read `@Check([name,] expression)` into a check constraint.
+*/
+pub fn check_options_extractor(arguments: &[Value]) -> Value {
+    let (name, expression) = match arguments {
+        [name, expression, ..] => (expression_value(name), expression_value(expression)),
+        [expression] => (Value::Null, expression_value(expression)),
+        [] => (Value::Null, Value::Null),
+    };
+    json!({ "id": nanoid!(), "name": name, "expression": expression })
+}
+
+/**
+Read a class-level `@Index([name,] [properties], options)` into a table index.
+Property names are resolved to the ids of the already parsed `columns`, names that don't
+resolve, e.g. of an inherited column, are kept as they are.
+*/
+pub fn index_options_extractor(arguments: &[Value], columns: &Value) -> Value {
+    let mut index = json!({
+        "id": nanoid!(),
+        "name": null,
+        "columns": [],
+        "unique": false,
+        "where": null,
+        "type": null
+    });
+    let empty_array = Vec::new();
+
+    for argument in arguments {
+        let argument_type = argument["type"].as_str().unwrap_or("");
+        match argument_type {
+            "Literal" => index["name"] = argument["value"].clone(),
+            "ArrayExpression" => {
+                let column_ids: Vec<Value> = argument["elements"]
+                    .as_array()
+                    .unwrap_or(&empty_array)
+                    .iter()
+                    .filter_map(|element| element["value"].as_str())
+                    .map(|name| {
+                        columns
+                            .as_array()
+                            .unwrap_or(&empty_array)
+                            .iter()
+                            .find(|col| col["name"] == name)
+                            .map_or(json!(name), |col| col["id"].clone())
+                    })
+                    .collect();
+                index["columns"] = json!(column_ids);
+            }
+            "ObjectExpression" => {
+                let index_options = argument["properties"].as_array().unwrap_or(&empty_array);
+                for option in index_options {
+                    let key = option["key"]["name"].as_str().unwrap_or("");
+                    let value = &option["value"]["value"];
+                    match key {
+                        "unique" => index["unique"] = json!(value.as_bool().unwrap_or(false)),
+                        "where" => index["where"] = value.clone(),
+                        "fulltext" if value == true => index["type"] = json!("fulltext"),
+                        "spatial" if value == true => index["type"] = json!("spatial"),
+                        _ => {} // do nothing
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    index
+}
@@ -0,0 +1,410 @@
+use serde_json::Value;
+
+// Words that share the same singular and plural form.
+const UNCOUNTABLES: [&str; 20] = [
+    "audio",
+    "data",
+    "deer",
+    "equipment",
+    "feedback",
+    "fish",
+    "information",
+    "media",
+    "metadata",
+    "money",
+    "moose",
+    "news",
+    "police",
+    "rice",
+    "series",
+    "sheep",
+    "software",
+    "species",
+    "staff",
+    "traffic",
+];
+
+// (singular, plural) pairs that do not follow any suffix rule. Singular words ending in an
+// `s`, e.g. `gas`, plurals ending in `-ches` that drop only the `s` and the `-f` words turning
+// into `-ves` are listed here, `golf` and `chef` just take an `s`.
+const IRREGULARS: [(&str, &str); 42] = [
+    ("person", "people"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("child", "children"),
+    ("tooth", "teeth"),
+    ("foot", "feet"),
+    ("mouse", "mice"),
+    ("goose", "geese"),
+    ("ox", "oxen"),
+    ("leaf", "leaves"),
+    ("half", "halves"),
+    ("self", "selves"),
+    ("shelf", "shelves"),
+    ("wolf", "wolves"),
+    ("calf", "calves"),
+    ("elf", "elves"),
+    ("knife", "knives"),
+    ("life", "lives"),
+    ("hero", "heroes"),
+    ("potato", "potatoes"),
+    ("tomato", "tomatoes"),
+    ("movie", "movies"),
+    ("quiz", "quizzes"),
+    ("criterion", "criteria"),
+    ("datum", "data"),
+    ("medium", "media"),
+    ("index", "indices"),
+    ("matrix", "matrices"),
+    ("vertex", "vertices"),
+    ("axis", "axes"),
+    ("alias", "aliases"),
+    ("atlas", "atlases"),
+    ("bias", "biases"),
+    ("canvas", "canvases"),
+    ("gas", "gases"),
+    ("lens", "lenses"),
+    ("avalanche", "avalanches"),
+    ("cache", "caches"),
+    ("headache", "headaches"),
+    ("moustache", "moustaches"),
+    ("niche", "niches"),
+    ("psyche", "psyches"),
+];
+
+/**
+English pluralisation/singularisation for table, relation and join table names.
+
+User-supplied overrides (`{ "singular": "plural" }`) take precedence over the
+built-in irregular forms, which take precedence over the suffix rules.
+*/
+pub struct Inflector {
+    overrides: Vec<(String, String)>,
+}
+
+impl Inflector {
+    pub fn new(overrides: &Value) -> Inflector {
+        let mut pairs: Vec<(String, String)> = Vec::new();
+        if let Some(map) = overrides.as_object() {
+            for (singular, plural) in map {
+                if let Some(plural) = plural.as_str() {
+                    pairs.push((singular.to_lowercase(), plural.to_lowercase()));
+                }
+            }
+        }
+        Inflector { overrides: pairs }
+    }
+
+    /**
+    Plural form of `word`, e.g. `Category` -> `Categories`, `OrderItem` -> `OrderItems`.
+    */
+    pub fn pluralize(&self, word: &str) -> String {
+        self.inflect(word, true)
+    }
+
+    /**
+    Singular form of `word`, e.g. `categories` -> `category`, `people` -> `person`.
+    */
+    pub fn singularize(&self, word: &str) -> String {
+        self.inflect(word, false)
+    }
+
+    fn inflect(&self, word: &str, plural: bool) -> String {
+        // only the last segment of a compound name is inflected.
+        let (head, tail) = split_last_word(word);
+        if tail.is_empty() {
+            return word.to_string();
+        }
+        let lower = tail.to_lowercase();
+
+        let inflected = if UNCOUNTABLES.contains(&lower.as_str()) {
+            lower.clone()
+        } else if let Some(found) = self.lookup(&lower, plural) {
+            found
+        } else if plural {
+            pluralize_rule(&lower)
+        } else {
+            singularize_rule(&lower)
+        };
+
+        format!("{}{}", head, match_case(tail, &inflected))
+    }
+
+    fn lookup(&self, word: &str, plural: bool) -> Option<String> {
+        let irregulars = IRREGULARS
+            .iter()
+            .map(|(s, p)| (s.to_string(), p.to_string()));
+        for (singular, plural_form) in self.overrides.iter().cloned().chain(irregulars) {
+            if plural && (word == singular || word == plural_form) {
+                return Some(plural_form);
+            }
+            if !plural && (word == plural_form || word == singular) {
+                return Some(singular);
+            }
+        }
+        None
+    }
+}
+
+fn pluralize_rule(word: &str) -> String {
+    let consonant_before_y = word.ends_with('y')
+        && word
+            .chars()
+            .rev()
+            .nth(1)
+            .map(|c| !"aeiou".contains(c))
+            .unwrap_or(false);
+
+    if word.ends_with("sis") {
+        // analysis -> analyses
+        format!("{}es", &word[..word.len() - 2])
+    } else if consonant_before_y {
+        format!("{}ies", &word[..word.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|s| word.ends_with(s))
+    {
+        format!("{}es", word)
+    } else {
+        format!("{}s", word)
+    }
+}
+
+fn singularize_rule(word: &str) -> String {
+    let stem = |suffix_len: usize| &word[..word.len() - suffix_len];
+
+    if word.ends_with("yses") {
+        // analyses -> analysis
+        format!("{}is", stem(2))
+    } else if word.ends_with("ies") && word.len() > 4 {
+        format!("{}y", stem(3))
+    } else if ["sses", "xes", "zes", "ches", "shes"]
+        .iter()
+        .any(|s| word.ends_with(s))
+    {
+        stem(2).to_string()
+    } else if word.ends_with("uses") {
+        // statuses -> status, but houses -> house.
+        let vowel_before = stem(4)
+            .chars()
+            .last()
+            .map(|c| "aeiou".contains(c))
+            .unwrap_or(false);
+        if vowel_before {
+            stem(1).to_string()
+        } else {
+            stem(2).to_string()
+        }
+    } else if word.ends_with('s') && !["ss", "us", "is"].iter().any(|s| word.ends_with(s)) {
+        // analysis, axis and iris are singular already.
+        stem(1).to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/**
+Split `word` into everything before its last word segment and the segment itself.
+`OrderItem` -> (`Order`, `Item`), `order_item` -> (`order_`, `item`). Acronyms are a segment
+of their own, `HTTPRequest` -> (`HTTP`, `Request`).
+*/
+fn split_last_word(word: &str) -> (&str, &str) {
+    let mut start = 0;
+    let chars: Vec<(usize, char)> = word.char_indices().collect();
+    for (idx, (pos, c)) in chars.iter().enumerate() {
+        if *c == '_' || *c == '-' || *c == ' ' {
+            start = pos + c.len_utf8();
+        } else if c.is_uppercase() && idx > 0 && is_word_start(&chars, idx) {
+            start = *pos;
+        }
+    }
+    word.split_at(start)
+}
+
+/**
+Whether the uppercase character at `idx` starts a word: it follows a lowercase character, or
+ends an acronym and is followed by a lowercase one, the `R` of `HTTPRequest`.
+*/
+fn is_word_start(chars: &[(usize, char)], idx: usize) -> bool {
+    let previous = chars[idx - 1].1;
+    if !previous.is_uppercase() {
+        return true;
+    }
+    chars
+        .get(idx + 1)
+        .is_some_and(|(_, next)| next.is_lowercase())
+}
+
+fn match_case(original: &str, inflected: &str) -> String {
+    if original.len() > 1 && original.chars().all(|c| !c.is_lowercase()) {
+        return inflected.to_uppercase();
+    }
+    if original
+        .chars()
+        .next()
+        .map(|c| c.is_uppercase())
+        .unwrap_or(false)
+    {
+        let mut chars = inflected.chars();
+        return match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+    }
+    inflected.to_string()
+}
+
+/**
+`OrderItem` / `order_item` -> `orderItem`, a leading acronym is lowercased as a whole:
+`HTTPRequest` -> `httpRequest`, `URL` -> `url`.
+*/
+pub fn to_camel_case(word: &str) -> String {
+    let pascal = to_pascal_case(word);
+    let chars: Vec<char> = pascal.chars().collect();
+    let leading_uppercase = chars.iter().take_while(|c| c.is_uppercase()).count();
+    // the last capital of an acronym followed by a lowercase character starts the next word.
+    let lowered = match chars.get(leading_uppercase) {
+        Some(next) if leading_uppercase > 1 && next.is_lowercase() => leading_uppercase - 1,
+        _ => leading_uppercase.max(1),
+    };
+    chars
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, c)| {
+            if idx < lowered {
+                c.to_lowercase().collect::<Vec<char>>()
+            } else {
+                vec![*c]
+            }
+        })
+        .collect()
+}
+
+/**
+`order_item` / `orderItem` -> `OrderItem`.
+*/
+pub fn to_pascal_case(word: &str) -> String {
+    word.split(['_', '-', ' '])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/**
+`OrderItem` / `orderItem` -> `order_item`.
+*/
+pub fn to_snake_case(word: &str) -> String {
+    let mut snake = String::new();
+    let chars: Vec<char> = word.chars().collect();
+    for (idx, c) in chars.iter().enumerate() {
+        if *c == '-' || *c == ' ' {
+            snake.push('_');
+        } else if c.is_uppercase() {
+            let prev_lower =
+                idx > 0 && (chars[idx - 1].is_lowercase() || chars[idx - 1].is_numeric());
+            let next_lower = chars
+                .get(idx + 1)
+                .map(|n| n.is_lowercase())
+                .unwrap_or(false);
+            if idx > 0 && chars[idx - 1] != '_' && (prev_lower || next_lower) {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(*c);
+        }
+    }
+    snake
+}
+
+/**
+`OrderItem` / `order_item` -> `order-item`.
+*/
+pub fn to_kebab_case(word: &str) -> String {
+    to_snake_case(word).replace('_', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pluralizes() {
+        let inflector = Inflector::new(&Value::Null);
+        for (singular, plural) in [
+            ("user", "users"),
+            ("Category", "Categories"),
+            ("OrderItem", "OrderItems"),
+            ("order_item", "order_items"),
+            ("box", "boxes"),
+            ("branch", "branches"),
+            ("analysis", "analyses"),
+            ("person", "people"),
+            ("gas", "gases"),
+            ("Canvas", "Canvases"),
+            ("axis", "axes"),
+            ("lens", "lenses"),
+            ("cache", "caches"),
+            ("news", "news"),
+            ("HTTPRequest", "HTTPRequests"),
+            ("BookShelf", "BookShelves"),
+            ("golf", "golfs"),
+        ] {
+            assert_eq!(inflector.pluralize(singular), plural, "{}", singular);
+        }
+    }
+
+    #[test]
+    fn singularizes() {
+        let inflector = Inflector::new(&Value::Null);
+        for (plural, singular) in [
+            ("users", "user"),
+            ("Categories", "Category"),
+            ("boxes", "box"),
+            ("branches", "branch"),
+            ("statuses", "status"),
+            ("houses", "house"),
+            ("analyses", "analysis"),
+            ("people", "person"),
+            ("caches", "cache"),
+            ("HTTPRequests", "HTTPRequest"),
+            ("wolves", "wolf"),
+            ("valves", "valve"),
+        ] {
+            assert_eq!(inflector.singularize(plural), singular, "{}", plural);
+        }
+        // singular words ending in an s stay as they are.
+        for word in [
+            "analysis", "axis", "gas", "Canvas", "Alias", "lens", "status", "address",
+        ] {
+            assert_eq!(inflector.singularize(word), word);
+        }
+    }
+
+    #[test]
+    fn overrides_take_precedence() {
+        let inflector = Inflector::new(&json!({ "cactus": "cacti", "person": "persons" }));
+        assert_eq!(inflector.pluralize("Cactus"), "Cacti");
+        assert_eq!(inflector.singularize("cacti"), "cactus");
+        assert_eq!(inflector.pluralize("person"), "persons");
+    }
+
+    #[test]
+    fn acronyms_are_words() {
+        assert_eq!(split_last_word("HTTPRequest"), ("HTTP", "Request"));
+        assert_eq!(split_last_word("ProfileURL"), ("Profile", "URL"));
+        assert_eq!(to_camel_case("HTTPRequest"), "httpRequest");
+        assert_eq!(to_camel_case("URL"), "url");
+        assert_eq!(to_camel_case("order_item"), "orderItem");
+        assert_eq!(to_snake_case("HTTPRequest"), "http_request");
+        assert_eq!(to_kebab_case("OrderItem"), "order-item");
+        assert_eq!(to_pascal_case("order_item"), "OrderItem");
+    }
+}
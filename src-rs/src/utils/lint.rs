@@ -0,0 +1,173 @@
+use crate::helpers;
+use crate::inflection;
+use crate::types;
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+/**
+Warnings about a model that compiles but won't work against a database, as a JSON array of
+`{ level, table, column, message }` where `table` and `column` are node ids, `column` is `null`
+for table wide warnings.
+*/
+pub fn lint(json_str: &str, options_str: &str) -> String {
+    let dialect = types::DIALECTS[types::dialect(&helpers::parse_json(options_str))];
+    let empty_array = Vec::new();
+    let nodes = helpers::parse_json(json_str);
+    let tables: Vec<&Value> = nodes
+        .as_array()
+        .unwrap_or(&empty_array)
+        .iter()
+        .map(|node| &node["data"])
+        .collect();
+
+    let mut warnings: Vec<Value> = Vec::new();
+    for table in &tables {
+        warnings.extend(view_warnings(table, &tables));
+        warnings.extend(generation_warnings(table, dialect));
+    }
+    helpers::stringify_json(json!(warnings))
+}
+
+fn warning(table: &Value, column: Option<&Value>, message: String) -> Value {
+    json!({
+        "level": "warning",
+        "table": table["id"],
+        "column": column.map(|column| column["id"].clone()),
+        "message": message
+    })
+}
+
+/**
+Tables a view selects from that aren't in the model, either by database name or schema qualified.
+Names defined by a `WITH` clause are the view's own and never reported, and neither is the `FROM`
+of functions like `EXTRACT(YEAR FROM created_at)`.
+*/
+fn view_warnings(table: &Value, tables: &[&Value]) -> Vec<Value> {
+    let Some(expression) = table["expression"]
+        .as_str()
+        .filter(|_| table["kind"] == "view")
+    else {
+        return Vec::new();
+    };
+    let source_pattern = Regex::new(r#"(?i)\b(?:from|join)\s+([\w."`\[\]]+)"#).unwrap();
+    let cte_pattern = Regex::new(r"(?i)\b(\w+)\s+as\s*\(").unwrap();
+    let ctes: Vec<String> = cte_pattern
+        .captures_iter(expression)
+        .map(|captures| captures[1].to_lowercase())
+        .collect();
+
+    let mut unknown: Vec<String> = Vec::new();
+    for captures in source_pattern.captures_iter(expression) {
+        if is_function_argument(&expression[..captures.get(0).unwrap().start()]) {
+            continue;
+        }
+        let reference = captures[1].replace(['"', '`', '[', ']'], "").to_lowercase();
+        if reference.is_empty()
+            || ctes.contains(&reference)
+            || unknown.contains(&reference)
+            || tables.iter().any(|other| is_named(other, &reference))
+        {
+            continue;
+        }
+        unknown.push(reference);
+    }
+
+    unknown
+        .into_iter()
+        .map(|reference| {
+            warning(
+                table,
+                None,
+                format!(
+                    "View selects from \"{}\", which is not in the model",
+                    reference
+                ),
+            )
+        })
+        .collect()
+}
+
+/**
+Dialects that can create a column with the generation strategy, following TypeORM: `identity` is
+Postgres only, `rowid` is CockroachDB's and SQLite's own row id, and MongoDB keys are object ids.
+*/
+fn supported_dialects(strategy: &str) -> &'static [&'static str] {
+    match strategy {
+        "increment" | "uuid" => &["postgres", "mysql", "sqlite", "mssql"],
+        "identity" => &["postgres"],
+        "rowid" => &["sqlite"],
+        _ => &[],
+    }
+}
+
+/**
+Columns whose `generated` strategy the dialect can't create. Implied strategies, e.g. `increment`
+for an undecorated key, aren't a choice and aren't reported.
+*/
+fn generation_warnings(table: &Value, dialect: &str) -> Vec<Value> {
+    let empty_array = Vec::new();
+    let mut warnings = Vec::new();
+    for column in table["columns"].as_array().unwrap_or(&empty_array) {
+        if let Some(strategy) = column["generated"].as_str().filter(|s| !s.is_empty()) {
+            if !supported_dialects(strategy).contains(&dialect) {
+                warnings.push(warning(
+                    table,
+                    Some(column),
+                    format!(
+                        "{} does not support the \"{}\" generation strategy",
+                        dialect, strategy
+                    ),
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/**
+Whether the text ends inside the parentheses of a function taking a `FROM` argument.
+*/
+fn is_function_argument(before: &str) -> bool {
+    let mut depth = 0;
+    for (index, char) in before.char_indices().rev() {
+        match char {
+            ')' => depth += 1,
+            '(' if depth > 0 => depth -= 1,
+            '(' => {
+                let function = before[..index].trim_end();
+                let function = function
+                    .rsplit(|c: char| !c.is_alphanumeric() && c != '_')
+                    .next()
+                    .unwrap_or("")
+                    .to_lowercase();
+                return ["extract", "substring", "trim", "overlay", "position"]
+                    .contains(&function.as_str());
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/**
+Whether `reference`, a lowercase `name` or `schema.name`, is the database name of `table`.
+Unnamed tables use the snake case class name, like the entity decorator leaves it to TypeORM.
+*/
+fn is_named(table: &Value, reference: &str) -> bool {
+    let db_name = table["dbName"]
+        .as_str()
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .unwrap_or_else(|| inflection::to_snake_case(table["name"].as_str().unwrap_or("")))
+        .to_lowercase();
+    match reference.rsplit_once('.') {
+        Some((schema, name)) => {
+            name == db_name
+                && table["schema"]
+                    .as_str()
+                    .is_none_or(|table_schema| table_schema.to_lowercase() == schema)
+        }
+        None => reference == db_name,
+    }
+}
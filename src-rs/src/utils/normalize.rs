@@ -0,0 +1,330 @@
+use crate::helpers;
+use crate::inflection;
+
+use serde_json::{json, Value};
+
+/**
+Canonical form of a node list, used to compare models produced by different compilers.
+
+Differences that are semantically irrelevant are erased:
+- node ids, positions and column -> table back references, ids are replaced by names.
+- table order, and whether values were stored as strings (`"true"`, `"255"`), including
+  default values of numeric and boolean columns.
+- empty strings vs `null`, `0` vs `null` precision/scale, an empty `dbName` vs the default name.
+- options a primary key implies (`unique`, `nullable`) and options a relation property can't carry.
+- the legacy `autoIncrement` flag vs the `generated` strategy of a primary key.
+- default many-to-many join table names vs the same name written out.
+- the default prefix of an embedded class vs the property name written out.
+- column options of views, which only map the selected columns.
+*/
+pub fn normalize(json_str: &str, options_str: &str) -> String {
+    let options = helpers::parse_json(options_str);
+    let inflector = inflection::Inflector::new(&options["inflections"]);
+    helpers::stringify_json(normalize_nodes(&helpers::parse_json(json_str), &inflector))
+}
+
+pub fn normalize_nodes(nodes: &Value, inflector: &inflection::Inflector) -> Value {
+    let empty_array = Vec::new();
+    let tables: Vec<&Value> = nodes
+        .as_array()
+        .unwrap_or(&empty_array)
+        .iter()
+        .map(|node| &node["data"])
+        .collect();
+
+    let mut normalized: Vec<Value> = tables
+        .iter()
+        .map(|table| normalize_table(table, &tables, inflector))
+        .collect();
+    normalized.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    json!(normalized)
+}
+
+fn normalize_table(table: &Value, tables: &[&Value], inflector: &inflection::Inflector) -> Value {
+    let empty_array = Vec::new();
+    let name = text(&table["name"]).unwrap_or_default();
+    let columns = table["columns"].as_array().unwrap_or(&empty_array);
+    let column_name = |id: &Value| -> Value {
+        columns
+            .iter()
+            .find(|col| col["id"] == *id)
+            .map(|col| col["name"].clone())
+            .unwrap_or(Value::Null)
+    };
+
+    let is_composite_key = columns
+        .iter()
+        .filter(|col| flag(&col["primaryKey"], false) && col["foreignKey"].is_null())
+        .count()
+        > 1;
+
+    let indexes: Vec<Value> = table["indexes"]
+        .as_array()
+        .unwrap_or(&empty_array)
+        .iter()
+        .map(|index| {
+            // entries that aren't column ids are property names, e.g. of an inherited column.
+            let index_columns: Vec<Value> = index["columns"]
+                .as_array()
+                .unwrap_or(&empty_array)
+                .iter()
+                .map(|id| match column_name(id) {
+                    Value::Null => id.clone(),
+                    name => name,
+                })
+                .collect();
+            json!({
+                "name": text(&index["name"]),
+                "columns": index_columns,
+                "unique": flag(&index["unique"], false),
+                "where": text(&index["where"]),
+                // btree is what every dialect creates when no type is given.
+                "type": text(&index["type"]).filter(|t| t != "btree"),
+            })
+        })
+        .collect();
+
+    let order_by: Vec<Value> = table["orderBy"]
+        .as_array()
+        .unwrap_or(&empty_array)
+        .iter()
+        .map(|order| {
+            json!({
+                "column": column_name(&order["column"]),
+                "order": text(&order["order"]).unwrap_or("ASC".to_string()),
+            })
+        })
+        .collect();
+
+    let is_view = table["kind"] == "view";
+    let normalized_columns: Vec<Value> = columns
+        .iter()
+        .map(|column| normalize_column(column, &name, is_composite_key, tables, inflector))
+        .map(|column| {
+            // view columns only map a selected column, options have no meaning.
+            if is_view {
+                json!({ "name": column["name"], "dbName": column["dbName"], "dataType": column["dataType"] })
+            } else {
+                column
+            }
+        })
+        .collect();
+
+    json!({
+        "name": name,
+        // typeORM names tables after the snake_cased class by default.
+        "dbName": text(&table["dbName"]).unwrap_or(inflection::to_snake_case(&name)),
+        "kind": text(&table["kind"]).unwrap_or("entity".to_string()),
+        "tree": text(&table["tree"]),
+        "expression": text(&table["expression"]).map(|expression| expression.trim().to_string()),
+        "extends": table["extends"].as_object().map(|extends| {
+            let parent = tables
+                .iter()
+                .find(|parent| parent["id"] == extends["table"])
+                .and_then(|parent| text(&parent["name"]))
+                .or_else(|| extends.get("tableName").and_then(text));
+            json!({
+                "table": parent,
+                "strategy": extends.get("strategy").and_then(text).unwrap_or("class-table".to_string()),
+                "discriminator": extends.get("discriminator").and_then(text),
+            })
+        }),
+        "inheritance": table["inheritance"].as_object().map(|inheritance| {
+            json!({
+                "column": inheritance.get("column").and_then(text).unwrap_or("type".to_string()),
+                "type": inheritance.get("type").and_then(text).unwrap_or("varchar".to_string()),
+            })
+        }),
+        "description": text(&table["description"]),
+        "timestamps": flag(&table["timestamps"], true),
+        "engine": text(&table["engine"]).unwrap_or("InnoDB".to_string()),
+        "schema": text(&table["schema"]),
+        "database": text(&table["database"]),
+        "synchronize": flag(&table["synchronize"], true),
+        "orderBy": order_by,
+        "indexes": indexes,
+        "checks": checks(&table["checks"]),
+        "columns": normalized_columns,
+    })
+}
+
+fn normalize_column(
+    column: &Value,
+    table_name: &str,
+    is_composite_key: bool,
+    tables: &[&Value],
+    inflector: &inflection::Inflector,
+) -> Value {
+    let name = text(&column["name"]).unwrap_or_default();
+    let db_name = text(&column["dbName"]).unwrap_or(name.clone());
+    let is_primary = flag(&column["primaryKey"], false);
+
+    if let Some(fk) = column["foreignKey"].as_object() {
+        let join_type = text(&fk["type"]).unwrap_or("many-to-one".to_string());
+        let target_table = tables
+            .iter()
+            .find(|table| table["id"] == fk["target"]["table"]);
+        let target_table_name = text(&fk["target"]["tableName"])
+            .or_else(|| target_table.and_then(|table| text(&table["name"])));
+        let target_column_name = text(&fk["target"]["columnName"])
+            .or_else(|| {
+                target_table
+                    .and_then(|table| table["columns"].as_array())
+                    .and_then(|cols| cols.iter().find(|col| col["id"] == fk["target"]["column"]))
+                    .and_then(|col| text(&col["name"]))
+            })
+            .or_else(|| {
+                Some(helpers::default_inverse_name(
+                    table_name, &join_type, inflector,
+                ))
+            });
+
+        let through = match join_type.as_str() {
+            "many-to-many" => Some(text(&fk["through"]).unwrap_or_else(|| {
+                helpers::default_join_table_name(
+                    table_name,
+                    target_table_name.as_deref().unwrap_or(""),
+                    inflector,
+                )
+            })),
+            _ => None,
+        };
+
+        // only the owning side of a relation has a column of its own.
+        let owns_column = join_type == "many-to-one" || join_type == "one-to-one";
+
+        return json!({
+            "name": name,
+            "dbName": if owns_column { Some(db_name) } else { None },
+            "index": flag(&column["index"], false),
+            "checks": checks(&column["checks"]),
+            "foreignKey": {
+                "type": join_type,
+                "target": {
+                    "table": target_table_name,
+                    "column": target_column_name,
+                },
+                "onDelete": text(&fk["onDelete"]).unwrap_or("SET NULL".to_string()),
+                "onUpdate": text(&fk["onUpdate"]).unwrap_or("CASCADE".to_string()),
+                "through": through,
+                "joinColumn": fk.get("joinColumn").cloned().unwrap_or(Value::Null),
+                "inverseColumn": fk.get("inverseColumn").cloned().unwrap_or(Value::Null),
+            },
+        });
+    }
+
+    if let Some(embedded) = column["embedded"].as_object() {
+        let embedded_table = tables
+            .iter()
+            .find(|table| table["id"] == embedded["table"])
+            .and_then(|table| text(&table["name"]))
+            .or_else(|| embedded.get("tableName").and_then(text));
+        // no prefix option means the property name is the prefix.
+        let prefix = match embedded.get("prefix") {
+            Some(Value::String(prefix)) if prefix.is_empty() => json!(false),
+            Some(Value::String(prefix)) => json!(prefix),
+            Some(Value::Bool(false)) => json!(false),
+            _ => json!(name),
+        };
+        return json!({
+            "name": name,
+            "embedded": { "table": embedded_table, "prefix": prefix },
+        });
+    }
+
+    let generated = if is_primary {
+        if flag(&column["autoIncrement"], false) && column.get("generated").is_none() {
+            Some("increment".to_string())
+        } else {
+            helpers::generation_strategy(column, is_composite_key)
+        }
+    } else {
+        text(&column["generated"])
+    };
+    let identity_generation = match generated.as_deref() {
+        Some("identity") => text(&column["identityGeneration"]),
+        _ => None,
+    };
+
+    let column_enum: Option<Vec<Value>> = column["enum"]
+        .as_array()
+        .filter(|values| !values.is_empty())
+        .cloned();
+
+    json!({
+        "name": name,
+        "dbName": db_name,
+        "dataType": text(&column["dataType"]).unwrap_or("string".to_string()),
+        "primaryKey": is_primary,
+        "generated": generated,
+        "identityGeneration": identity_generation,
+        "index": flag(&column["index"], false),
+        "checks": checks(&column["checks"]),
+        // primary keys are implicitly unique and not nullable.
+        "unique": !is_primary && flag(&column["unique"], false),
+        "nullable": !is_primary && flag(&column["nullable"], false),
+        "defaultValue": match helpers::default_expression(column) {
+            Some(expression) => json!(expression),
+            None => helpers::typed_default(column),
+        },
+        "defaultKind": if helpers::default_expression(column).is_some() { "expression" } else { "literal" },
+        "length": number(&column["length"]).unwrap_or(255),
+        "precision": number(&column["precision"]).filter(|n| *n > 0),
+        "scale": number(&column["scale"]).filter(|n| *n > 0),
+        "collation": text(&column["collation"]),
+        "description": text(&column["description"]),
+        "autoIncrement": !is_primary && flag(&column["autoIncrement"], false),
+        "select": flag(&column["select"], true),
+        "zerofill": flag(&column["zerofill"], false),
+        "enum": column_enum,
+        "enumName": text(&column["enumName"]),
+        "hstoreType": text(&column["hstoreType"]),
+        "array": flag(&column["array"], false),
+    })
+}
+
+/**
+Check constraints without their ids.
+*/
+fn checks(checks: &Value) -> Value {
+    let empty_array = Vec::new();
+    let checks: Vec<Value> = checks
+        .as_array()
+        .unwrap_or(&empty_array)
+        .iter()
+        .map(|check| json!({ "name": text(&check["name"]), "expression": text(&check["expression"]) }))
+        .collect();
+    json!(checks)
+}
+
+/**
+Non-empty string value, `None` for null and empty strings.
+*/
+fn text(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+/**
+Boolean value, accepting the `"true"` / `"false"` strings older parsers produced.
+*/
+fn flag(value: &Value, default: bool) -> bool {
+    match value {
+        Value::Bool(value) => *value,
+        Value::String(value) => value.as_str() == "true",
+        _ => default,
+    }
+}
+
+/**
+Numeric value, accepting numeric strings older parsers produced.
+*/
+fn number(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(value) => value.as_u64(),
+        Value::String(value) => value.parse().ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,106 @@
+use serde_json::{json, Value};
+
+// private use characters delimiting marked regions of generated code.
+const OPEN_TABLE: char = '\u{E000}';
+const OPEN_COLUMN: char = '\u{E001}';
+const END_OF_ID: char = '\u{E002}';
+const CLOSE: char = '\u{E003}';
+
+/**
+Mark `code` as emitted for the table `id`. Leading whitespace is left outside the region.
+*/
+pub fn mark_table(id: &str, code: &str) -> String {
+    mark(OPEN_TABLE, id, code)
+}
+
+/**
+Mark `code` as emitted for the column `id`. Leading whitespace is left outside the region.
+*/
+pub fn mark_column(id: &str, code: &str) -> String {
+    mark(OPEN_COLUMN, id, code)
+}
+
+/**
+Opening marker of a table region, for code that is spliced in rather than generated in one piece.
+*/
+pub fn open_table(id: &str) -> String {
+    format!("{}{}{}", OPEN_TABLE, id, END_OF_ID)
+}
+
+/**
+Closing marker of the innermost open region.
+*/
+pub fn close_region() -> String {
+    CLOSE.to_string()
+}
+
+fn mark(open: char, id: &str, code: &str) -> String {
+    let body = code.trim_start();
+    let indent = &code[..code.len() - body.len()];
+    format!("{}{}{}{}{}{}", indent, open, id, END_OF_ID, body, CLOSE)
+}
+
+/**
+Strip the region markers from generated `code`, returning the plain code and its source map:
+one `{ id, kind, table?, start, end, startLine, endLine }` entry per marked region. `start` / `end`
+are byte offsets, lines are 1-based and inclusive. Column entries carry the id of their table.
+*/
+pub fn extract(code: &str) -> (String, Vec<Value>) {
+    let mut plain = String::with_capacity(code.len());
+    let mut entries: Vec<Value> = Vec::new();
+    // (entry index, kind) of the regions currently open.
+    let mut open: Vec<(usize, char)> = Vec::new();
+    let mut line = 1;
+
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            OPEN_TABLE | OPEN_COLUMN => {
+                let id: String = chars.by_ref().take_while(|c| *c != END_OF_ID).collect();
+                let mut entry = json!({
+                    "id": id,
+                    "kind": if c == OPEN_TABLE { "table" } else { "column" },
+                    "start": plain.len(),
+                    "startLine": line,
+                });
+                if let Some((table_idx, _)) =
+                    open.iter().rev().find(|(_, kind)| *kind == OPEN_TABLE)
+                {
+                    entry["table"] = entries[*table_idx]["id"].clone();
+                }
+                open.push((entries.len(), c));
+                entries.push(entry);
+            }
+            CLOSE => {
+                if let Some((idx, _)) = open.pop() {
+                    entries[idx]["end"] = json!(plain.len());
+                    entries[idx]["endLine"] = json!(line);
+                }
+            }
+            _ => {
+                if c == '\n' {
+                    line += 1;
+                }
+                plain.push(c);
+            }
+        }
+    }
+    (plain, entries)
+}
+
+/**
+Reverse mapping entry from the ESTree `node` a table or column was created from, to its id.
+`range` and `loc` are copied as the parser reported them.
+*/
+pub fn ast_entry(kind: &str, id: &Value, table: Option<&Value>, node: &Value) -> Value {
+    let mut entry = json!({
+        "id": id,
+        "kind": kind,
+        "range": node["range"],
+        "loc": node["loc"],
+    });
+    if let Some(table) = table {
+        entry["table"] = table.clone();
+    }
+    entry
+}
@@ -0,0 +1,312 @@
+use serde_json::Value;
+
+/**
+Databases code is generated for, in the order of `LogicalType::native`.
+*/
+pub const DIALECTS: [&str; 5] = ["postgres", "mysql", "sqlite", "mssql", "mongodb"];
+
+/**
+A model data type: its TS type, Zod schema and class-validator decorator, and the native column
+type in every dialect, `None` where the dialect has no equivalent. Length, precision and scale are
+separate column options.
+*/
+struct LogicalType {
+    name: &'static str,
+    ts_type: &'static str,
+    zod: &'static str,
+    validator: Option<&'static str>,
+    native: [Option<&'static str>; 5],
+}
+
+// new logical types are added here, the first type of a native type wins when parsing it back.
+const LOGICAL_TYPES: [LogicalType; 19] = [
+    LogicalType {
+        name: "string",
+        ts_type: "string",
+        zod: "z.string()",
+        validator: Some("IsString"),
+        native: [
+            Some("varchar"),
+            Some("varchar"),
+            Some("varchar"),
+            Some("nvarchar"),
+            Some("string"),
+        ],
+    },
+    LogicalType {
+        name: "text",
+        ts_type: "string",
+        zod: "z.string()",
+        validator: Some("IsString"),
+        native: [
+            Some("text"),
+            Some("text"),
+            Some("text"),
+            Some("text"),
+            Some("string"),
+        ],
+    },
+    LogicalType {
+        name: "number",
+        ts_type: "number",
+        zod: "z.number().int()",
+        validator: Some("IsInt"),
+        native: [
+            Some("integer"),
+            Some("int"),
+            Some("integer"),
+            Some("int"),
+            Some("int"),
+        ],
+    },
+    // drivers return 64 bit integers as strings.
+    LogicalType {
+        name: "bigint",
+        ts_type: "string",
+        zod: "z.string()",
+        validator: Some("IsNumberString"),
+        native: [
+            Some("bigint"),
+            Some("bigint"),
+            Some("bigint"),
+            Some("bigint"),
+            Some("long"),
+        ],
+    },
+    LogicalType {
+        name: "float",
+        ts_type: "number",
+        zod: "z.number()",
+        validator: Some("IsNumber"),
+        native: [
+            Some("double precision"),
+            Some("double"),
+            Some("real"),
+            Some("float"),
+            Some("double"),
+        ],
+    },
+    // money-like values stay strings in TS, a number would lose precision.
+    LogicalType {
+        name: "decimal",
+        ts_type: "string",
+        zod: "z.string()",
+        validator: Some("IsDecimal"),
+        native: [
+            Some("numeric"),
+            Some("decimal"),
+            Some("decimal"),
+            Some("decimal"),
+            Some("decimal128"),
+        ],
+    },
+    LogicalType {
+        name: "boolean",
+        ts_type: "boolean",
+        zod: "z.boolean()",
+        validator: Some("IsBoolean"),
+        native: [
+            Some("boolean"),
+            Some("boolean"),
+            Some("boolean"),
+            Some("bit"),
+            Some("bool"),
+        ],
+    },
+    LogicalType {
+        name: "date",
+        ts_type: "Date",
+        zod: "z.coerce.date()",
+        validator: Some("IsDateString"),
+        native: [
+            Some("timestamp"),
+            Some("datetime"),
+            Some("datetime"),
+            Some("datetime2"),
+            Some("date"),
+        ],
+    },
+    // drivers return date-only values as `YYYY-MM-DD` strings.
+    LogicalType {
+        name: "dateOnly",
+        ts_type: "string",
+        zod: "z.string().date()",
+        validator: Some("IsDateString"),
+        native: [
+            Some("date"),
+            Some("date"),
+            Some("date"),
+            Some("date"),
+            Some("date"),
+        ],
+    },
+    LogicalType {
+        name: "timestamptz",
+        ts_type: "Date",
+        zod: "z.coerce.date()",
+        validator: Some("IsDateString"),
+        native: [
+            Some("timestamp with time zone"),
+            Some("timestamp"),
+            Some("datetime"),
+            Some("datetimeoffset"),
+            Some("date"),
+        ],
+    },
+    LogicalType {
+        name: "time",
+        ts_type: "string",
+        zod: "z.string()",
+        validator: Some("IsString"),
+        native: [Some("time"), Some("time"), Some("time"), Some("time"), None],
+    },
+    LogicalType {
+        name: "json",
+        ts_type: "any",
+        zod: "z.any()",
+        validator: Some("IsObject"),
+        native: [
+            Some("json"),
+            Some("json"),
+            Some("text"),
+            Some("nvarchar"),
+            Some("object"),
+        ],
+    },
+    LogicalType {
+        name: "jsonb",
+        ts_type: "any",
+        zod: "z.any()",
+        validator: Some("IsObject"),
+        native: [
+            Some("jsonb"),
+            Some("json"),
+            Some("text"),
+            Some("nvarchar"),
+            Some("object"),
+        ],
+    },
+    LogicalType {
+        name: "uuid",
+        ts_type: "string",
+        zod: "z.string().uuid()",
+        validator: Some("IsUUID"),
+        native: [
+            Some("uuid"),
+            Some("varchar"),
+            Some("varchar"),
+            Some("uniqueidentifier"),
+            Some("string"),
+        ],
+    },
+    LogicalType {
+        name: "objectId",
+        ts_type: "string",
+        zod: "z.string()",
+        validator: Some("IsMongoId"),
+        native: [None, None, None, None, Some("objectId")],
+    },
+    LogicalType {
+        name: "inet",
+        ts_type: "string",
+        zod: "z.string().ip()",
+        validator: Some("IsIP"),
+        native: [
+            Some("inet"),
+            Some("varchar"),
+            Some("varchar"),
+            Some("varchar"),
+            Some("string"),
+        ],
+    },
+    LogicalType {
+        name: "interval",
+        ts_type: "string",
+        zod: "z.string()",
+        validator: Some("IsString"),
+        native: [Some("interval"), None, None, None, None],
+    },
+    LogicalType {
+        name: "binary",
+        ts_type: "Buffer",
+        zod: "z.instanceof(Buffer)",
+        validator: None,
+        native: [
+            Some("bytea"),
+            Some("blob"),
+            Some("blob"),
+            Some("varbinary"),
+            Some("binData"),
+        ],
+    },
+    // enum columns are typed with their generated enum.
+    LogicalType {
+        name: "enum",
+        ts_type: "string",
+        zod: "z.string()",
+        validator: Some("IsString"),
+        native: [
+            Some("enum"),
+            Some("enum"),
+            Some("simple-enum"),
+            Some("simple-enum"),
+            None,
+        ],
+    },
+];
+
+/**
+Index of the `dialect` option in `DIALECTS`, Postgres when it is missing or unknown.
+*/
+pub fn dialect(options: &Value) -> usize {
+    let dialect = options["dialect"].as_str().unwrap_or("");
+    DIALECTS.iter().position(|d| *d == dialect).unwrap_or(0)
+}
+
+fn logical(data_type: &str) -> Option<&'static LogicalType> {
+    LOGICAL_TYPES.iter().find(|t| t.name == data_type)
+}
+
+/**
+Native column type of a model data type in `dialect`. Types the registry doesn't know, e.g.
+`geometry`, are native types already, types the dialect can't store are kept as they are.
+*/
+pub fn native_type(data_type: &str, dialect: usize) -> &str {
+    logical(data_type)
+        .and_then(|t| t.native[dialect])
+        .unwrap_or(data_type)
+}
+
+/**
+Model data type of a native column type of `dialect`, the inverse of `native_type`.
+Native types win over logical type names, Postgres `date` is `dateOnly` and not `date`.
+Logical type names `dialect` doesn't use natively and unknown types are kept.
+*/
+pub fn logical_type(native: &str, dialect: usize) -> &str {
+    LOGICAL_TYPES
+        .iter()
+        .find(|t| t.native[dialect] == Some(native))
+        .map_or(native, |t| t.name)
+}
+
+/**
+TS type of the property of a model data type, `unknown` for types the registry doesn't know.
+*/
+pub fn ts_type(data_type: &str) -> &str {
+    logical(data_type).map_or("unknown", |t| t.ts_type)
+}
+
+/**
+Zod schema of a model data type, `z.unknown()` for types the registry doesn't know.
+JSON carries dates as ISO strings, they are coerced.
+*/
+pub fn zod_schema(data_type: &str) -> &str {
+    logical(data_type).map_or("z.unknown()", |t| t.zod)
+}
+
+/**
+class-validator decorator of a model data type, if its values can be validated.
+*/
+pub fn validator(data_type: &str) -> Option<&'static str> {
+    logical(data_type).and_then(|t| t.validator)
+}
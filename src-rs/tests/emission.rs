@@ -0,0 +1,216 @@
+//! One focused check of the code each feature emits, for the corpus case of the feature.
+//! Round trips of the same cases are checked in `tests/round_trip.rs`.
+
+mod support;
+
+use serde_json::json;
+use src_rs::{convert_from_typeorm, convert_to_typeorm, normalize};
+use support::corpus::case;
+
+fn assert_emits(code: &str, expected: &[&str]) {
+    for expected in expected {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+}
+
+#[test]
+fn enums_are_declared_once_and_referenced() {
+    let nodes = case("enums").to_string();
+    let code = convert_to_typeorm(&nodes, None);
+    assert_emits(
+        &code,
+        &[
+            "export enum UserRole {\n    ADMIN = \"admin\",\n    IN_REVIEW = \"in review\",\n}",
+            "enum: UserRole, enumName: \"user_role\" })\n    role: UserRole;",
+            "enum: UserStatus })\n    status: UserStatus;",
+        ],
+    );
+    assert_eq!(code.matches("export enum UserRole").count(), 1, "{}", code);
+    assert!(code.find("export enum") < code.find("@Entity"), "{}", code);
+
+    // string literal unions read back as the same enum columns.
+    let options = json!({ "enumStyle": "union" }).to_string();
+    let code = convert_to_typeorm(&nodes, Some(options));
+    assert_emits(
+        &code,
+        &[
+            "export type UserRole = \"admin\" | \"in review\";",
+            "enum: [\"admin\", \"in review\"]",
+        ],
+    );
+    let parsed = convert_from_typeorm(&support::parse_program(&code).to_string(), None);
+    assert_eq!(
+        normalize(&parsed, None),
+        normalize(&nodes, None),
+        "{}",
+        code
+    );
+}
+
+#[test]
+fn defaults_are_typed() {
+    let code = convert_to_typeorm(&case("defaults").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "default: 5 })\n    retries: number;",
+            "default: 0.5 })\n    ratio: number;",
+            "default: true })\n    enabled: boolean;",
+            "default: \"say \\\"hi\\\"\" })\n    label: string;",
+            "default: () => \"now()\" })\n    createdAt: Date;",
+            "default: \"12\" })\n    code: string;",
+        ],
+    );
+}
+
+#[test]
+fn descriptions_are_jsdoc_and_comments() {
+    let code = convert_to_typeorm(&case("descriptions").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "/** Invoices sent to customers. */\n@Entity({ name: \"invoice\", comment: \"Invoices sent to customers.\" })",
+            "    /** Invoice number. */\n    @PrimaryGeneratedColumn({ comment: \"Invoice number.\" })",
+            "    /**\n     * Free text.\n     * Never shown to the customer, see *\\/ too.\n     */\n    @Column(",
+            "comment: \"Free text.\\nNever shown to the customer, see */ too.\" })",
+        ],
+    );
+    assert_eq!(code.matches("/**").count(), 3, "{}", code);
+}
+
+#[test]
+fn blank_descriptions_are_left_out() {
+    let mut nodes = case("scalar columns");
+    nodes[0]["data"]["description"] = json!("  ");
+    nodes[0]["data"]["columns"][0]["description"] = json!("\n");
+    nodes[0]["data"]["columns"][1]["description"] = json!(" ");
+    let code = convert_to_typeorm(&nodes.to_string(), None);
+
+    assert!(!code.contains("/**"), "{}", code);
+    assert!(!code.contains("comment:"), "{}", code);
+}
+
+#[test]
+fn embeddables_are_plain_classes() {
+    let code = convert_to_typeorm(&case("embeddables").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "    @Column(() => Address)\n    billing: Address;",
+            "    @Column(() => Address, { prefix: \"ship_\" })\n    shipping: Address;",
+            "    @Column(() => Address, { prefix: false })\n    home: Address;",
+        ],
+    );
+    // the embedding class references the embeddable when the module loads.
+    assert!(
+        code.find("export class Address {") < code.find("export class Customer {"),
+        "{}",
+        code
+    );
+    assert_eq!(code.matches("@Entity(").count(), 1, "{}", code);
+}
+
+#[test]
+fn hierarchies_declare_parents_first() {
+    let code = convert_to_typeorm(&case("inheritance").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "export abstract class Base {\n    @PrimaryGeneratedColumn()",
+            "@Entity({ name: \"post\" })\nexport class Post extends Base {",
+            "@Entity({ name: \"content\" })\n@TableInheritance({ column: { type: \"varchar\", name: \"kind\" } })\nexport class Content extends Base {",
+            "@ChildEntity(\"photo\")\nexport class Photo extends Content {",
+            "@ChildEntity()\nexport class Video extends Content {",
+        ],
+    );
+    let position = |class: &str| code.find(&format!("class {} ", class)).unwrap();
+    assert!(position("Base") < position("Post"), "{}", code);
+    assert!(position("Content") < position("Photo"), "{}", code);
+}
+
+#[test]
+fn tree_tables_use_tree_decorators() {
+    let code = convert_to_typeorm(&case("trees").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "@Entity({ name: \"category\" })\n@Tree(\"closure-table\")\nexport class Category {",
+            "    @TreeParent({ onDelete: \"CASCADE\" })\n    @JoinColumn({ name: \"parent_id\", referencedColumnName: \"id\" })\n    parent: Category;",
+            "    @TreeChildren()\n    children: Category[];",
+        ],
+    );
+    assert!(!code.contains("ManyToOne"), "{}", code);
+}
+
+#[test]
+fn checks_are_decorators() {
+    let code = convert_to_typeorm(&case("checks").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "@Entity({ name: \"product\" })\n@Check(\"CHK_price_below_list\", \"\\\"price\\\" <= \\\"listPrice\\\"\")\nexport class Product {",
+            "    @Column({ name: \"price\", type: \"integer\" })\n    @Check(\"\\\"price\\\" >= 0\")\n    price: number;",
+            "    @Check(\"CHK_sku_format\", \"\\\"sku\\\" ~ '^[A-Z]{3}-[0-9]+$'\")\n    sku: string;",
+        ],
+    );
+}
+
+#[test]
+fn views_are_view_entities() {
+    let code = convert_to_typeorm(&case("views").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "@ViewEntity({ name: \"active_users\", expression: `SELECT u.id, u.email AS contact\nFROM user u\nWHERE u.active` })\nexport class ActiveUser {",
+            "    @ViewColumn()\n    id: number;",
+            "    @ViewColumn({ name: \"contact\" })\n    email: string;",
+            "@ViewEntity({ name: \"user_count\", expression: \"SELECT count(*) AS total FROM user\" })",
+        ],
+    );
+}
+
+#[test]
+fn join_tables_live_in_the_owners_schema() {
+    let code = convert_to_typeorm(&case("schemas").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "@Entity({ name: \"user\", schema: \"auth\" })",
+            "@Entity({ name: \"invoice\", schema: \"billing\" })",
+            "@JoinTable({ name: \"user_plans\", schema: \"auth\" })",
+        ],
+    );
+}
+
+#[test]
+fn generated_columns_use_generated_decorators() {
+    let code = convert_to_typeorm(&case("generated columns").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "    @PrimaryGeneratedColumn(\"identity\", { generatedIdentity: \"ALWAYS\" })\n    id: number;",
+            "    @Column({ name: \"publicId\", type: \"uuid\" })\n    @Generated(\"uuid\")\n    publicId: string;",
+            "    @Column({ name: \"number\", type: \"integer\", generatedIdentity: \"BY DEFAULT\" })\n    @Generated(\"identity\")\n    number: number;",
+        ],
+    );
+}
+
+#[test]
+fn relations_are_named_after_the_target() {
+    let code = convert_to_typeorm(&case("inflected names").to_string(), None);
+    assert_emits(
+        &code,
+        &[
+            "@ManyToOne(() => Canvas, (canvas) => canvas.id,",
+            "@ManyToMany(() => HTTPRequest, (httpRequest) => httpRequest.id,",
+            "@JoinTable({ name: \"post_http_requests\" })",
+            "@ManyToOne(() => Person, (person) => person.posts,",
+            "@OneToMany(() => Post, (post) => post.person,",
+        ],
+    );
+}
@@ -0,0 +1,201 @@
+//! Multi-file output: one entity per file, with imports between them.
+
+mod support;
+
+use serde_json::{json, Value};
+use src_rs::convert_to_typeorm;
+use support::corpus::{case, node, primary, relation, table};
+
+fn schema() -> String {
+    let mut user = table(
+        "t1",
+        "User",
+        vec![
+            primary("c1", "id", "number", Some("increment")),
+            relation(
+                "c2",
+                "orderItems",
+                "one-to-many",
+                ("t2", "OrderItem"),
+                ("c4", "user"),
+            ),
+        ],
+    );
+    user["dbName"] = json!("users");
+    let mut order_item = table(
+        "t2",
+        "OrderItem",
+        vec![
+            primary("c3", "id", "number", Some("increment")),
+            relation("c4", "user", "many-to-one", ("t1", "User"), ("c1", "id")),
+            relation(
+                "c5",
+                "parent",
+                "many-to-one",
+                ("t2", "OrderItem"),
+                ("c3", "id"),
+            ),
+        ],
+    );
+    order_item["dbName"] = json!("order_items");
+    json!([node(user), node(order_item)]).to_string()
+}
+
+fn files(options: Value) -> Value {
+    serde_json::from_str(&convert_to_typeorm(&schema(), Some(options.to_string()))).unwrap()
+}
+
+/**
+Files of the corpus case `name`.
+*/
+fn case_files(name: &str, options: Value) -> Value {
+    let nodes = case(name).to_string();
+    serde_json::from_str(&convert_to_typeorm(&nodes, Some(options.to_string()))).unwrap()
+}
+
+#[test]
+fn one_entity_per_file() {
+    let files = files(json!({ "output": "files" }));
+
+    assert_eq!(files.as_object().unwrap().len(), 2);
+    let order_item = files["order-item.entity.ts"].as_str().unwrap();
+    assert!(order_item.starts_with(
+        "import { Entity, JoinColumn, ManyToOne, PrimaryGeneratedColumn } from \"typeorm\";\nimport { User } from \"./user.entity\";\n\n@Entity"
+    ));
+    // self references are not imported.
+    assert_eq!(order_item.matches("import ").count(), 2);
+    assert!(!order_item.contains("class User "));
+    assert!(files["user.entity.ts"]
+        .as_str()
+        .unwrap()
+        .contains("import { OrderItem } from \"./order-item.entity\";"));
+}
+
+#[test]
+fn file_naming_and_barrel() {
+    let files =
+        files(json!({ "output": "files", "fileName": "entities/{pascal}.ts", "barrel": true }));
+
+    assert!(files["entities/User.ts"]
+        .as_str()
+        .unwrap()
+        .contains("import { OrderItem } from \"./OrderItem\";"));
+    assert_eq!(
+        files["index.ts"],
+        "export * from \"./entities/User\";\nexport * from \"./entities/OrderItem\";"
+    );
+}
+
+#[test]
+fn file_source_maps() {
+    let output = files(json!({ "output": "files", "sourceMap": true }));
+    let code = output["files"]["user.entity.ts"].as_str().unwrap();
+    let entry = &output["sourceMap"]["user.entity.ts"][0];
+
+    assert_eq!(entry["id"], "t1");
+    let start = entry["start"].as_u64().unwrap() as usize;
+    assert!(code[start..].starts_with("@Entity"));
+}
+
+#[test]
+fn colliding_paths_are_numbered() {
+    let key = |id: &str| primary(id, "id", "number", Some("increment"));
+    let nodes = json!([
+        node(table("t1", "OrderItem", vec![key("c1")])),
+        node(table("t2", "Order_Item", vec![key("c2")])),
+        node(table("t3", "Index", vec![key("c3")])),
+    ]);
+    let options = json!({ "output": "files", "fileName": "{kebab}.ts", "barrel": true });
+    let files: Value = serde_json::from_str(&convert_to_typeorm(
+        &nodes.to_string(),
+        Some(options.to_string()),
+    ))
+    .unwrap();
+
+    assert_eq!(files.as_object().unwrap().len(), 4, "{}", files);
+    assert!(files["order-item.ts"]
+        .as_str()
+        .unwrap()
+        .contains("class OrderItem "));
+    assert!(files["order-item-2.ts"]
+        .as_str()
+        .unwrap()
+        .contains("class Order_Item "));
+    assert!(files["index-2.ts"]
+        .as_str()
+        .unwrap()
+        .contains("class Index "));
+    assert_eq!(
+        files["index.ts"],
+        "export * from \"./order-item\";\nexport * from \"./order-item-2\";\nexport * from \"./index-2\";"
+    );
+}
+
+#[test]
+fn enums_are_declared_in_the_first_file_using_them() {
+    let files = case_files("enums", json!({ "output": "files" }));
+
+    assert!(files["user.entity.ts"]
+        .as_str()
+        .unwrap()
+        .contains("export enum UserRole {"));
+    let invite = files["invite.entity.ts"].as_str().unwrap();
+    assert!(invite.contains("import { UserRole } from \"./user.entity\";"));
+    assert!(!invite.contains("export enum"));
+}
+
+#[test]
+fn embedding_entities_import_embeddables() {
+    let files = case_files("embeddables", json!({ "output": "files" }));
+
+    let customer = files["customer.entity.ts"].as_str().unwrap();
+    assert!(
+        customer.contains("import { Address } from \"./address.entity\";"),
+        "{}",
+        customer
+    );
+    let address = files["address.entity.ts"].as_str().unwrap();
+    assert!(
+        address.starts_with("import { Column } from \"typeorm\";"),
+        "{}",
+        address
+    );
+}
+
+#[test]
+fn child_files_import_their_parent() {
+    let files = case_files("inheritance", json!({ "output": "files" }));
+
+    let photo = files["photo.entity.ts"].as_str().unwrap();
+    assert!(
+        photo.contains("import { ChildEntity, Column } from \"typeorm\";\nimport { Content } from \"./content.entity\";"),
+        "{}",
+        photo
+    );
+}
+
+#[test]
+fn files_are_grouped_by_schema() {
+    let files = case_files(
+        "schemas",
+        json!({ "output": "files", "fileName": "{schema}/{kebab}.entity.ts" }),
+    );
+
+    let mut paths: Vec<&String> = files.as_object().unwrap().keys().collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            "auth/user.entity.ts",
+            "billing/invoice.entity.ts",
+            "billing/plan.entity.ts",
+            "setting.entity.ts"
+        ]
+    );
+    let invoice = files["billing/invoice.entity.ts"].as_str().unwrap();
+    assert!(
+        invoice.contains("import { User } from \"../auth/user.entity\";"),
+        "{}",
+        invoice
+    );
+}
@@ -0,0 +1,104 @@
+//! Generated code imports exactly the typeORM decorators it uses.
+
+mod support;
+
+use serde_json::json;
+use src_rs::convert_to_typeorm;
+use support::corpus::{column, node, primary, relation, table};
+
+fn schema() -> String {
+    let mut user = table(
+        "t1",
+        "User",
+        vec![
+            primary("c1", "id", "number", Some("increment")),
+            column("c2", "email", "string"),
+        ],
+    );
+    user["dbName"] = json!("users");
+    user["indexes"] = json!([{ "id": "i1", "name": null, "columns": ["c2"], "unique": true }]);
+    let mut post = table(
+        "t2",
+        "Post",
+        vec![
+            primary("c3", "id", "uuid", Some("uuid")),
+            relation("c4", "author", "many-to-one", ("t1", "User"), ("c1", "id")),
+        ],
+    );
+    post["dbName"] = json!("posts");
+    json!([node(user), node(post)]).to_string()
+}
+
+#[test]
+fn imports_used_decorators_sorted() {
+    let code = convert_to_typeorm(&schema(), None);
+    assert!(
+        code.starts_with(
+            "import { Column, Entity, Index, JoinColumn, ManyToOne, PrimaryGeneratedColumn } from \"typeorm\";\n\n@Entity"
+        ),
+        "{}",
+        code
+    );
+    assert_eq!(code.matches("import ").count(), 1);
+}
+
+#[test]
+fn merged_import_keeps_other_names() {
+    let previous = r#"import { BaseEntity, Entity, OneToMany } from "typeorm";
+import { Expose } from "class-transformer";
+
+@Entity()
+export class User extends BaseEntity {
+    @Expose()
+    @Column()
+    email: string;
+}"#;
+    let options = json!({
+        "previousSource": previous,
+        "previousProgram": support::parse_program(previous),
+    });
+    let code = convert_to_typeorm(&schema(), Some(options.to_string()));
+
+    assert!(
+        code.starts_with(
+            "import { BaseEntity, Column, Entity, Index, JoinColumn, ManyToOne, PrimaryGeneratedColumn } from \"typeorm\";\nimport { Expose } from \"class-transformer\";"
+        ),
+        "{}",
+        code
+    );
+    assert!(
+        code.contains("export class User extends BaseEntity {"),
+        "{}",
+        code
+    );
+}
+
+#[test]
+fn merged_import_keeps_aliases_and_drops_unused_decorators() {
+    let previous = r#"import { BeforeInsert, Column as Col, Entity, Tree } from "typeorm";
+
+// @Unique() in a comment isn't imported.
+@Entity()
+export class User {
+    @Column()
+    email: string;
+
+    @BeforeInsert()
+    lowercase() {
+        this.email = this.email.toLowerCase();
+    }
+}"#;
+    let options = json!({
+        "previousSource": previous,
+        "previousProgram": support::parse_program(previous),
+    });
+    let code = convert_to_typeorm(&schema(), Some(options.to_string()));
+
+    assert!(
+        code.starts_with(
+            "import { BeforeInsert, Column, Column as Col, Entity, Index, JoinColumn, ManyToOne, PrimaryGeneratedColumn } from \"typeorm\";\n"
+        ),
+        "{}",
+        code
+    );
+}
@@ -0,0 +1,91 @@
+//! Warnings about models that compile but won't work against the database.
+
+mod support;
+
+use serde_json::{json, Value};
+use src_rs::lint;
+use support::corpus;
+
+fn warnings(nodes: &Value, options: Value) -> Vec<Value> {
+    let warnings: Value =
+        serde_json::from_str(&lint(&nodes.to_string(), Some(options.to_string()))).unwrap();
+    warnings.as_array().unwrap().clone()
+}
+
+#[test]
+fn corpus_has_no_warnings() {
+    // the rowid key of the generation strategies is CockroachDB and SQLite only, see below.
+    for (name, nodes) in corpus::corpus()
+        .into_iter()
+        .filter(|(name, _)| *name != "generation strategies")
+    {
+        assert_eq!(warnings(&nodes, json!({})), Vec::<Value>::new(), "{}", name);
+    }
+}
+
+#[test]
+fn views_warn_about_unknown_tables() {
+    let mut nodes = corpus::case("views");
+    nodes[1]["data"]["expression"] = json!(
+        "WITH recent AS (SELECT * FROM \"user\" WHERE EXTRACT(YEAR FROM created_at) > 2020)\n\
+         SELECT r.id, r.email AS contact FROM recent r JOIN public.accounts a ON a.user_id = r.id"
+    );
+
+    assert_eq!(
+        warnings(&nodes, json!({})),
+        vec![json!({
+            "level": "warning",
+            "table": "t2",
+            "column": null,
+            "message": "View selects from \"public.accounts\", which is not in the model"
+        })]
+    );
+}
+
+#[test]
+fn generation_strategies_warn_per_dialect() {
+    let nodes = corpus::case("generation strategies");
+    let unsupported = |options: Value| -> Vec<(Value, Value)> {
+        warnings(&nodes, options)
+            .into_iter()
+            .map(|warning| (warning["table"].clone(), warning["message"].clone()))
+            .collect()
+    };
+
+    assert_eq!(
+        unsupported(json!({})),
+        vec![(
+            json!("t4"),
+            json!("postgres does not support the \"rowid\" generation strategy")
+        )]
+    );
+    assert_eq!(
+        unsupported(json!({ "dialect": "mysql" })),
+        vec![
+            (
+                json!("t3"),
+                json!("mysql does not support the \"identity\" generation strategy")
+            ),
+            (
+                json!("t4"),
+                json!("mysql does not support the \"rowid\" generation strategy")
+            )
+        ]
+    );
+}
+
+#[test]
+fn rowid_is_native_to_sqlite() {
+    let nodes = corpus::case("generation strategies");
+    let messages: Vec<Value> = warnings(&nodes, json!({ "dialect": "sqlite" }))
+        .into_iter()
+        .map(|warning| warning["message"].clone())
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![json!(
+            "sqlite does not support the \"identity\" generation strategy"
+        )]
+    );
+}
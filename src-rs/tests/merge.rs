@@ -0,0 +1,277 @@
+//! Regenerating into previously generated, hand-edited code keeps the user's code.
+
+mod support;
+
+use serde_json::{json, Value};
+use src_rs::{convert_from_typeorm, convert_to_typeorm, normalize};
+use support::corpus::case;
+
+fn options(previous_source: &str) -> Option<String> {
+    Some(
+        json!({
+            "previousSource": previous_source,
+            "previousProgram": support::parse_program(previous_source),
+        })
+        .to_string(),
+    )
+}
+
+fn nodes_of(code: &str) -> Value {
+    serde_json::from_str(&convert_from_typeorm(
+        &support::parse_program(code).to_string(),
+        None,
+    ))
+    .unwrap()
+}
+
+const EDITED: &str = r#"import { Entity, Column, BeforeInsert } from "typeorm";
+import { Expose } from "class-transformer";
+
+// users of the shop.
+@Entity({ name: "users" })
+export class User {
+    @PrimaryGeneratedColumn()
+    id: number;
+
+    @Expose()
+    @Column()
+    name: string;
+
+    @Column()
+    nickname: string;
+
+    @BeforeInsert()
+    normalizeName() {
+        this.name = this.name.trim();
+    }
+
+    get displayName(): string {
+        return `${this.name}!`;
+    }
+}
+
+@Entity()
+export class Legacy {
+    @PrimaryGeneratedColumn()
+    id: number;
+}
+
+export function greet(user: User) {
+    return user.name;
+}"#;
+
+#[test]
+fn regenerating_keeps_user_code() {
+    let mut nodes = nodes_of(EDITED);
+    let nodes_array = nodes.as_array_mut().unwrap();
+
+    // canvas edits: drop `Legacy`, drop `nickname`, make `name` unique, add `email` and `Order`.
+    nodes_array.retain(|node| node["data"]["name"] != "Legacy");
+    let columns = nodes_array[0]["data"]["columns"].as_array_mut().unwrap();
+    columns.retain(|col| col["name"] != "nickname");
+    columns[1]["unique"] = json!(true);
+    let mut email = columns[1].clone();
+    email["id"] = json!("email");
+    email["name"] = json!("email");
+    email["dbName"] = json!("email");
+    columns.push(email);
+    let mut order = nodes_array[0].clone();
+    order["id"] = json!("order");
+    order["data"]["name"] = json!("Order");
+    order["data"]["dbName"] = json!("orders");
+    nodes_array.push(order);
+
+    let input = nodes.to_string();
+    let code = convert_to_typeorm(&input, options(EDITED));
+
+    for kept in [
+        "import { Expose } from \"class-transformer\";",
+        "// users of the shop.",
+        "    @Expose()\n    @Column({ type: \"varchar\", unique: true })\n    name: string;",
+        "    @BeforeInsert()\n    normalizeName() {\n        this.name = this.name.trim();\n    }",
+        "get displayName(): string {",
+        "export function greet(user: User) {\n    return user.name;\n}",
+    ] {
+        assert!(code.contains(kept), "missing {:?} in:\n{}", kept, code);
+    }
+    assert!(!code.contains("nickname"), "{}", code);
+    assert!(!code.contains("class Legacy"), "{}", code);
+    assert!(
+        code.find("    email: string;") < code.find("@BeforeInsert"),
+        "{}",
+        code
+    );
+    assert!(code.contains("export class Order {"), "{}", code);
+
+    assert_eq!(
+        normalize(&nodes_of(&code).to_string(), None),
+        normalize(&input, None),
+        "{}",
+        code
+    );
+}
+
+#[test]
+fn regenerating_unchanged_model_is_stable() {
+    let nodes = nodes_of(EDITED).to_string();
+    let code = convert_to_typeorm(&nodes, options(EDITED));
+    assert_eq!(code, convert_to_typeorm(&nodes, options(&code)));
+}
+
+#[test]
+fn merged_enums_are_updated_in_place() {
+    let previous = r#"// roles
+export enum UserRole {
+    ADMIN = "admin",
+}
+
+@Entity()
+export class User {
+    @PrimaryGeneratedColumn()
+    id: number;
+}"#;
+    let nodes = case("enums").to_string();
+    let code = convert_to_typeorm(&nodes, options(previous));
+
+    assert!(code.contains(
+        "// roles\nexport enum UserRole {\n    ADMIN = \"admin\",\n    IN_REVIEW = \"in review\",\n}\n\nexport enum UserStatus {"
+    ), "{}", code);
+    assert!(
+        code.find("export enum UserStatus") < code.find("@Entity"),
+        "{}",
+        code
+    );
+    assert_eq!(code.matches("export enum UserRole").count(), 1, "{}", code);
+    assert_eq!(
+        normalize(&nodes_of(&code).to_string(), None),
+        normalize(&nodes, None)
+    );
+}
+
+#[test]
+fn regenerating_replaces_generated_docs() {
+    let nodes = case("descriptions");
+    let code = convert_to_typeorm(&nodes.to_string(), None);
+    let edited = code.replace(
+        "    @Column({ name: \"total\"",
+        "    /** user docs are kept. */\n    @Column({ name: \"total\"",
+    );
+
+    let mut nodes = nodes;
+    nodes[0]["data"]["description"] = json!("Invoices, sent or not.");
+    let regenerated = convert_to_typeorm(&nodes.to_string(), options(&edited));
+
+    assert!(
+        regenerated.contains("/** Invoices, sent or not. */\n@Entity("),
+        "{}",
+        regenerated
+    );
+    assert!(!regenerated.contains("Invoices sent"), "{}", regenerated);
+    assert!(
+        regenerated.contains("/** user docs are kept. */\n    @Column("),
+        "{}",
+        regenerated
+    );
+    assert_eq!(regenerated.matches("/**").count(), 4, "{}", regenerated);
+    assert_eq!(
+        regenerated,
+        convert_to_typeorm(&nodes.to_string(), options(&regenerated))
+    );
+}
+
+#[test]
+fn regenerating_updates_the_parent() {
+    let mut nodes = case("inheritance");
+    let code = convert_to_typeorm(&nodes.to_string(), None);
+
+    nodes[0]["data"]["extends"] = Value::Null;
+    let regenerated = convert_to_typeorm(&nodes.to_string(), options(&code));
+
+    assert!(
+        regenerated.contains("export class Post {"),
+        "{}",
+        regenerated
+    );
+    assert!(
+        regenerated.contains("export abstract class Base {"),
+        "{}",
+        regenerated
+    );
+}
+
+#[test]
+fn regenerating_replaces_checks() {
+    let mut nodes = case("checks");
+    let code = convert_to_typeorm(&nodes.to_string(), None);
+
+    nodes[0]["data"]["checks"] = json!([]);
+    nodes[0]["data"]["columns"][1]["checks"][0]["expression"] = json!("\"price\" > 0");
+    let regenerated = convert_to_typeorm(&nodes.to_string(), options(&code));
+
+    assert!(
+        !regenerated.contains("CHK_price_below_list"),
+        "{}",
+        regenerated
+    );
+    assert!(
+        regenerated.contains("@Check(\"\\\"price\\\" > 0\")"),
+        "{}",
+        regenerated
+    );
+    assert_eq!(regenerated.matches("@Check(").count(), 2, "{}", regenerated);
+}
+
+#[test]
+fn removed_views_are_dropped_when_regenerating() {
+    let mut nodes = case("views");
+    let code = convert_to_typeorm(&nodes.to_string(), None);
+
+    nodes.as_array_mut().unwrap().pop();
+    let regenerated = convert_to_typeorm(&nodes.to_string(), options(&code));
+
+    assert!(!regenerated.contains("UserCount"), "{}", regenerated);
+    assert!(
+        regenerated.contains("export class ActiveUser {"),
+        "{}",
+        regenerated
+    );
+}
+
+#[test]
+fn columns_typeorm_sets_are_kept_verbatim() {
+    let previous = r#"@Entity()
+export class Post {
+    @PrimaryGeneratedColumn()
+    id: number;
+
+    @CreateDateColumn({ type: "timestamp" })
+    createdAt: Date;
+
+    @DeleteDateColumn()
+    deletedAt: Date | null;
+
+    @VersionColumn()
+    revision: number;
+}"#;
+    let mut nodes = nodes_of(previous);
+    let columns = nodes[0]["data"]["columns"].as_array().unwrap();
+    assert_eq!(columns[1]["defaultKind"], "expression", "{:#}", nodes);
+    assert_eq!(columns[2]["nullable"], true, "{:#}", nodes);
+    assert_eq!(columns[3]["dataType"], "number", "{:#}", nodes);
+
+    nodes[0]["data"]["columns"].as_array_mut().unwrap().pop();
+    let code = convert_to_typeorm(&nodes.to_string(), options(previous));
+
+    assert!(
+        code.contains("    @CreateDateColumn({ type: \"timestamp\" })\n    createdAt: Date;"),
+        "{}",
+        code
+    );
+    assert!(
+        code.contains("    @DeleteDateColumn()\n    deletedAt: Date | null;"),
+        "{}",
+        code
+    );
+    assert!(!code.contains("revision"), "{}", code);
+    assert_eq!(code.matches("@Column(").count(), 0, "{}", code);
+}
@@ -0,0 +1,247 @@
+//! nodes -> code -> nodes must describe the same schema, as defined by `normalize`.
+//!
+//! Every compiler pair (`convert_to_x` / `convert_from_x`) should be checked here.
+
+mod support;
+
+use proptest::prelude::*;
+use serde_json::{json, Value};
+use src_rs::{convert_from_typeorm, convert_to_typeorm, normalize};
+use support::corpus::{column, corpus, node, primary, relation, table};
+
+fn parse_typeorm(code: &str) -> String {
+    support::parse_program(code).to_string()
+}
+
+fn assert_round_trip(nodes: &Value) -> Result<(), TestCaseError> {
+    let input = nodes.to_string();
+    let code = convert_to_typeorm(&input, None);
+    let output = convert_from_typeorm(&parse_typeorm(&code), None);
+
+    let expected: Value = serde_json::from_str(&normalize(&input, None)).unwrap();
+    let actual: Value = serde_json::from_str(&normalize(&output, None)).unwrap();
+    prop_assert_eq!(
+        &actual,
+        &expected,
+        "\ngenerated code:\n{}\n\nexpected:\n{:#}\n\nactual:\n{:#}",
+        code,
+        expected,
+        actual
+    );
+    Ok(())
+}
+
+#[test]
+fn typeorm_round_trips_corpus() {
+    for (name, nodes) in corpus() {
+        if let Err(err) = assert_round_trip(&nodes) {
+            panic!("{}: {}", name, err);
+        }
+    }
+}
+
+// regenerating into the code itself finds every generated node again by its range.
+#[test]
+fn typeorm_regenerates_corpus_in_place() {
+    for (name, nodes) in corpus() {
+        let input = nodes.to_string();
+        let code = convert_to_typeorm(&input, None);
+        let options = json!({
+            "previousSource": code,
+            "previousProgram": support::parse_program(&code),
+        });
+        assert_eq!(
+            convert_to_typeorm(&input, Some(options.to_string())),
+            code,
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn normalize_ignores_ids_order_and_string_values() {
+    let a = json!([
+        node(table(
+            "t1",
+            "B",
+            vec![primary("c1", "id", "number", Some("increment"))]
+        )),
+        node(table(
+            "t2",
+            "A",
+            vec![primary("c2", "id", "number", Some("increment"))]
+        ))
+    ]);
+    let mut col = primary("x9", "id", "number", Some("increment"));
+    col["unique"] = json!("true");
+    col["length"] = json!("255");
+    let b = json!([
+        node(table("y1", "A", vec![col])),
+        node(table(
+            "y2",
+            "B",
+            vec![primary("x1", "id", "number", Some("increment"))]
+        ))
+    ]);
+    assert_eq!(
+        normalize(&a.to_string(), None),
+        normalize(&b.to_string(), None)
+    );
+}
+
+const NAMES: [&str; 8] = [
+    "title", "amount", "isActive", "slug", "rating", "notes", "code", "startsOn",
+];
+const DATA_TYPES: [&str; 6] = ["string", "number", "boolean", "date", "decimal", "text"];
+const TABLE_NAMES: [&str; 4] = ["Author", "Book", "Shelf", "Library"];
+const JOIN_TYPES: [&str; 3] = ["many-to-one", "one-to-one", "many-to-many"];
+
+fn arb_column(idx: usize) -> impl Strategy<Value = Value> {
+    (
+        0..DATA_TYPES.len(),
+        any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
+        prop::option::of(1u64..500),
+    )
+        .prop_map(move |(data_type, unique, nullable, index, length)| {
+            let mut col = column(&format!("c{}", idx), NAMES[idx], DATA_TYPES[data_type]);
+            col["unique"] = json!(unique);
+            col["nullable"] = json!(nullable);
+            col["index"] = json!(index);
+            if let Some(length) = length {
+                col["length"] = json!(length);
+            }
+            col
+        })
+}
+
+fn arb_schema() -> impl Strategy<Value = Value> {
+    (1..=TABLE_NAMES.len())
+        .prop_flat_map(|table_count| {
+            let tables = (0..table_count)
+                .map(|_| {
+                    (0..=NAMES.len()).prop_flat_map(|n| (0..n).map(arb_column).collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>();
+            let relations =
+                prop::collection::vec((0..table_count, 0..table_count, 0..JOIN_TYPES.len()), 0..3);
+            (tables, relations, prop::bool::ANY)
+        })
+        .prop_map(|(tables, relations, uuid_keys)| {
+            let mut tables: Vec<Value> = tables
+                .into_iter()
+                .enumerate()
+                .map(|(idx, mut columns)| {
+                    let key_type = if uuid_keys { "uuid" } else { "number" };
+                    let strategy = if uuid_keys { "uuid" } else { "increment" };
+                    columns.insert(
+                        0,
+                        primary(&format!("t{}_id", idx), "id", key_type, Some(strategy)),
+                    );
+                    table(&format!("t{}", idx), TABLE_NAMES[idx], columns)
+                })
+                .collect();
+
+            for (idx, (from, to, join_type)) in relations.into_iter().enumerate() {
+                let target = (format!("t{}", to), TABLE_NAMES[to]);
+                let target_column = format!("t{}_id", to);
+                let name = format!("rel{}", idx);
+                let col = relation(
+                    &format!("r{}", idx),
+                    &name,
+                    JOIN_TYPES[join_type],
+                    (&target.0, target.1),
+                    (&target_column, "id"),
+                );
+                tables[from]["columns"].as_array_mut().unwrap().push(col);
+            }
+
+            json!(tables.into_iter().map(node).collect::<Vec<_>>())
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn typeorm_round_trips_generated_schemas(nodes in arb_schema()) {
+        assert_round_trip(&nodes)?;
+    }
+}
+
+#[test]
+fn class_indexes_round_trip_from_code() {
+    // `tenantId` is a column of a base class the program doesn't include.
+    let code = r#"@Entity({ name: "account" })
+@Index("IDX_tenant_email", ["tenantId", "email"], { unique: true })
+export class Account {
+    @PrimaryGeneratedColumn()
+    id: number;
+
+    @Column({ type: "varchar", length: 255 })
+    email: string;
+}"#;
+    let parsed = convert_from_typeorm(&parse_typeorm(code), None);
+    let nodes: Value = serde_json::from_str(&parsed).unwrap();
+    let email = &nodes[0]["data"]["columns"][1]["id"];
+    assert_eq!(
+        nodes[0]["data"]["indexes"][0]["columns"],
+        json!(["tenantId", email])
+    );
+
+    let regenerated = convert_to_typeorm(&parsed, None);
+    assert!(
+        regenerated
+            .contains("@Index(\"IDX_tenant_email\", [\"tenantId\", \"email\"], { unique: true })"),
+        "{}",
+        regenerated
+    );
+    let reparsed = convert_from_typeorm(&parse_typeorm(&regenerated), None);
+    assert_eq!(normalize(&reparsed, None), normalize(&parsed, None));
+}
+
+#[test]
+fn template_literal_checks_are_read() {
+    let code = r#"@Entity()
+@Check("CHK_price", `"price" > 0`)
+export class Product {
+    @PrimaryGeneratedColumn()
+    id: number;
+
+    @Column()
+    @Check(`"stock" >= 0`)
+    stock: number;
+}"#;
+    let nodes: Value =
+        serde_json::from_str(&convert_from_typeorm(&parse_typeorm(code), None)).unwrap();
+
+    assert_eq!(nodes[0]["data"]["checks"][0]["name"], "CHK_price");
+    assert_eq!(nodes[0]["data"]["checks"][0]["expression"], "\"price\" > 0");
+    assert_eq!(
+        nodes[0]["data"]["columns"][1]["checks"][0]["expression"],
+        "\"stock\" >= 0"
+    );
+}
+
+#[test]
+fn undecorated_base_classes_are_abstract() {
+    let code = r#"
+export class Base {
+    @PrimaryGeneratedColumn()
+    id: number;
+}
+
+@Entity()
+export class User extends Base {
+    @Column()
+    name: string;
+}"#;
+    let parsed: Value =
+        serde_json::from_str(&convert_from_typeorm(&parse_typeorm(code), None)).unwrap();
+
+    assert_eq!(parsed[0]["data"]["kind"], "abstract");
+    assert_eq!(parsed[1]["data"]["extends"]["table"], parsed[0]["id"]);
+    assert_eq!(parsed[1]["data"]["extends"]["strategy"], "class-table");
+}
@@ -0,0 +1,129 @@
+//! Source maps from generated code to node/column ids, and from AST ranges back to ids.
+
+mod support;
+
+use serde_json::{json, Value};
+use src_rs::{convert_from_typeorm, convert_to_typeorm};
+use support::corpus::{column, node, primary, table};
+
+fn schema() -> Value {
+    let mut user = table(
+        "t1",
+        "User",
+        vec![
+            primary("c1", "id", "number", Some("increment")),
+            column("c2", "name", "string"),
+        ],
+    );
+    user["dbName"] = json!("users");
+    let mut tag = table(
+        "t2",
+        "Tag",
+        vec![
+            primary("c3", "id", "number", Some("increment")),
+            column("c4", "label", "string"),
+        ],
+    );
+    tag["dbName"] = json!("tags");
+    json!([node(user), node(tag)])
+}
+
+fn generate(options: Value) -> (String, Vec<Value>) {
+    let output: Value = serde_json::from_str(&convert_to_typeorm(
+        &schema().to_string(),
+        Some(options.to_string()),
+    ))
+    .unwrap();
+    (
+        output["code"].as_str().unwrap().to_string(),
+        output["sourceMap"].as_array().unwrap().clone(),
+    )
+}
+
+fn region<'a>(code: &'a str, source_map: &[Value], id: &str) -> &'a str {
+    let entry = source_map.iter().find(|entry| entry["id"] == id).unwrap();
+    let start = entry["start"].as_u64().unwrap() as usize;
+    let end = entry["end"].as_u64().unwrap() as usize;
+    let lines: Vec<&str> = code.lines().collect();
+    let start_line = entry["startLine"].as_u64().unwrap() as usize;
+    assert!(lines[start_line - 1].contains(code[start..end].lines().next().unwrap()));
+    &code[start..end]
+}
+
+#[test]
+fn generated_code_maps_tables_and_columns() {
+    let (code, source_map) = generate(json!({ "sourceMap": true }));
+
+    assert!(!code.contains('\u{E000}'));
+    assert_eq!(source_map.len(), 6);
+    assert!(region(&code, &source_map, "t2").starts_with("@Entity({ name: \"tags\" })"));
+    assert!(region(&code, &source_map, "t2").ends_with('}'));
+    assert_eq!(
+        region(&code, &source_map, "c4"),
+        "@Column({ name: \"label\", type: \"varchar\" })\n    label: string;"
+    );
+
+    let c4 = source_map.iter().find(|entry| entry["id"] == "c4").unwrap();
+    assert_eq!(c4["kind"], "column");
+    assert_eq!(c4["table"], "t2");
+    assert_eq!(
+        c4["endLine"].as_u64().unwrap(),
+        c4["startLine"].as_u64().unwrap() + 1
+    );
+}
+
+#[test]
+fn merged_code_maps_tables_and_columns() {
+    let (previous, _) = generate(json!({ "sourceMap": true }));
+    let previous = format!("// models\n{}", previous);
+    let (code, source_map) = generate(json!({
+        "sourceMap": true,
+        "previousSource": previous,
+        "previousProgram": support::parse_program(&previous),
+    }));
+
+    assert!(code.starts_with(
+        "// models\nimport { Column, Entity, PrimaryGeneratedColumn } from \"typeorm\";\n\n@Entity"
+    ));
+    assert!(region(&code, &source_map, "t1").starts_with("@Entity({ name: \"users\" })"));
+    assert!(region(&code, &source_map, "t1").ends_with('}'));
+    assert_eq!(
+        region(&code, &source_map, "c2"),
+        "@Column({ name: \"name\", type: \"varchar\" })\n    name: string;"
+    );
+}
+
+#[test]
+fn parsed_code_maps_ranges_to_ids() {
+    let code = convert_to_typeorm(&schema().to_string(), None);
+    let program = support::parse_program(&code);
+    let output: Value = serde_json::from_str(&convert_from_typeorm(
+        &program.to_string(),
+        Some(json!({ "sourceMap": true }).to_string()),
+    ))
+    .unwrap();
+
+    let tag = &output["nodes"][1];
+    let label = &tag["data"]["columns"][1];
+    let source_map = output["sourceMap"].as_array().unwrap();
+    let entry = source_map
+        .iter()
+        .find(|entry| entry["id"] == label["id"])
+        .unwrap();
+
+    assert_eq!(entry["kind"], "column");
+    assert_eq!(entry["table"], tag["id"]);
+    let start = entry["range"][0].as_u64().unwrap() as usize;
+    let end = entry["range"][1].as_u64().unwrap() as usize;
+    assert_eq!(
+        &code[start..end],
+        "@Column({ name: \"label\", type: \"varchar\" })\n    label: string;"
+    );
+
+    let table_entry = source_map
+        .iter()
+        .find(|entry| entry["id"] == tag["id"])
+        .unwrap();
+    let start = table_entry["range"][0].as_u64().unwrap() as usize;
+    assert!(code[start..].starts_with("@Entity({ name: \"tags\" })"));
+}
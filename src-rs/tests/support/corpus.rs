@@ -0,0 +1,636 @@
+//! Schemas every compiler pair is checked against, see `tests/round_trip.rs`, and the
+//! builders they are written with. Feature tests look their schema up with `case`.
+
+use serde_json::{json, Value};
+
+pub fn node(table: Value) -> Value {
+    json!({ "id": table["id"], "type": "table", "position": { "x": 0, "y": 0 }, "data": table })
+}
+
+pub fn column(id: &str, name: &str, data_type: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "dbName": name,
+        "dataType": data_type,
+        "primaryKey": false,
+        "generated": null,
+        "unique": false,
+        "nullable": false,
+        "index": false,
+        "defaultValue": null,
+        "length": 255,
+        "foreignKey": null
+    })
+}
+
+pub fn primary(id: &str, name: &str, data_type: &str, generated: Option<&str>) -> Value {
+    let mut col = column(id, name, data_type);
+    col["primaryKey"] = json!(true);
+    col["generated"] = json!(generated);
+    col
+}
+
+pub fn relation(
+    id: &str,
+    name: &str,
+    join_type: &str,
+    target: (&str, &str),
+    target_column: (&str, &str),
+) -> Value {
+    let mut col = column(id, name, "");
+    col["foreignKey"] = json!({
+        "type": join_type,
+        "target": {
+            "table": target.0,
+            "tableName": target.1,
+            "column": target_column.0,
+            "columnName": target_column.1
+        },
+        "through": null,
+        "onDelete": "CASCADE",
+        "onUpdate": "CASCADE"
+    });
+    col
+}
+
+pub fn table(id: &str, name: &str, columns: Vec<Value>) -> Value {
+    let primary_key: Vec<Value> = columns
+        .iter()
+        .filter(|col| col["primaryKey"] == true)
+        .map(|col| col["id"].clone())
+        .collect();
+    json!({
+        "id": id,
+        "name": name,
+        "dbName": name.to_lowercase(),
+        "columns": columns,
+        "primaryKey": primary_key,
+        "schema": null,
+        "database": null,
+        "synchronize": true,
+        "orderBy": [],
+        "indexes": []
+    })
+}
+
+pub fn corpus() -> Vec<(&'static str, Value)> {
+    let mut natural_key = primary("c1", "code", "string", None);
+    natural_key["length"] = json!(2);
+    let mut price = column("c3", "price", "decimal");
+    price["precision"] = json!(10);
+    price["scale"] = json!(2);
+    price["defaultValue"] = json!("0.00");
+    let mut status = column("c4", "status", "enum");
+    status["enum"] = json!(["draft", "published"]);
+    status["nullable"] = json!(true);
+    let mut email = column("c5", "email", "string");
+    email["unique"] = json!(true);
+    email["index"] = json!(true);
+    email["dbName"] = json!("email_address");
+
+    let mut indexed = table(
+        "t1",
+        "Account",
+        vec![
+            primary("c1", "id", "number", Some("increment")),
+            column("c2", "firstName", "string"),
+            column("c3", "lastName", "string"),
+        ],
+    );
+    indexed["indexes"] = json!([
+        { "id": "i1", "name": "IDX_full_name", "columns": ["c2", "c3"], "unique": true, "where": null, "type": null },
+        { "id": "i2", "name": null, "columns": ["c3"], "unique": false, "where": "\"lastName\" IS NOT NULL", "type": null }
+    ]);
+
+    let mut with_options = table(
+        "t1",
+        "AuditLog",
+        vec![
+            primary("c1", "id", "uuid", Some("uuid")),
+            column("c2", "createdAt", "date"),
+        ],
+    );
+    with_options["dbName"] = json!("audit_logs");
+    with_options["schema"] = json!("audit");
+    with_options["database"] = json!("logs");
+    with_options["engine"] = json!("MyISAM");
+    with_options["synchronize"] = json!(false);
+    with_options["orderBy"] = json!([{ "column": "c2", "order": "DESC" }]);
+
+    let mut identity = primary("c1", "id", "bigint", Some("identity"));
+    identity["identityGeneration"] = json!("ALWAYS");
+
+    // ranges count UTF-16 code units, astral characters take two.
+    let mut menu = table(
+        "t1",
+        "Menü",
+        vec![
+            primary("c1", "id", "number", Some("increment")),
+            column("c2", "größe", "string"),
+            column("c3", "price", "decimal"),
+        ],
+    );
+    menu["description"] = json!("Café menu ☕, prices in €.");
+    menu["columns"][1]["description"] = json!("Portion size 🍽️");
+    menu["columns"][1]["defaultValue"] = json!("groß 😀");
+    menu["columns"][2]["description"] = json!("Préis");
+
+    let enum_column = |id: &str, name: &str, values: Value, enum_name: Value| {
+        let mut col = column(id, name, "enum");
+        col["enum"] = values;
+        col["enumName"] = enum_name;
+        col
+    };
+
+    let with_default = |id: &str, name: &str, data_type: &str, default: Value, kind: &str| {
+        let mut col = column(id, name, data_type);
+        col["defaultValue"] = default;
+        col["defaultKind"] = json!(kind);
+        col
+    };
+
+    let mut invoice = table(
+        "t1",
+        "Invoice",
+        vec![
+            primary("c1", "id", "number", Some("increment")),
+            column("c2", "notes", "string"),
+            column("c3", "total", "number"),
+        ],
+    );
+    invoice["description"] = json!("Invoices sent to customers.");
+    invoice["columns"][0]["description"] = json!("Invoice number.");
+    invoice["columns"][1]["description"] =
+        json!("Free text.\nNever shown to the customer, see */ too.");
+
+    let embedded = |id: &str, name: &str, prefix: Value| {
+        let mut col = column(id, name, "string");
+        col["embedded"] = json!({ "table": "t2", "tableName": "Address", "prefix": prefix });
+        col
+    };
+    let mut address = table(
+        "t2",
+        "Address",
+        vec![
+            column("c5", "street", "string"),
+            column("c6", "zip", "string"),
+        ],
+    );
+    address["kind"] = json!("embeddable");
+
+    let extends = |table: &str, table_name: &str, strategy: &str, discriminator: Value| json!({ "table": table, "tableName": table_name, "strategy": strategy, "discriminator": discriminator });
+    // children first, the generator has to declare parents before them.
+    let mut post = table("t1", "Post", vec![column("c1", "title", "string")]);
+    post["extends"] = extends("t2", "Base", "class-table", Value::Null);
+    let mut base = table(
+        "t2",
+        "Base",
+        vec![
+            primary("c2", "id", "number", Some("increment")),
+            column("c3", "createdAt", "date"),
+        ],
+    );
+    base["kind"] = json!("abstract");
+    let mut photo = table("t3", "Photo", vec![column("c4", "size", "number")]);
+    photo["extends"] = extends("t4", "Content", "single-table", json!("photo"));
+    let mut content = table("t4", "Content", vec![column("c5", "title", "string")]);
+    content["extends"] = extends("t2", "Base", "class-table", Value::Null);
+    content["inheritance"] = json!({ "column": "kind", "type": "varchar" });
+    let mut video = table("t5", "Video", vec![column("c6", "length", "number")]);
+    video["extends"] = extends("t4", "Content", "single-table", Value::Null);
+
+    let tree = |id: &str, name: &str, strategy: &str| {
+        let key = format!("{}_c1", id);
+        let mut parent = relation(
+            &format!("{}_c3", id),
+            "parent",
+            "many-to-one",
+            (id, name),
+            (&key, "id"),
+        );
+        parent["dbName"] = json!("parent_id");
+        let children = relation(
+            &format!("{}_c4", id),
+            "children",
+            "one-to-many",
+            (id, name),
+            (&key, "id"),
+        );
+        let mut tree = table(
+            id,
+            name,
+            vec![
+                primary(&key, "id", "number", Some("increment")),
+                column(&format!("{}_c2", id), "name", "string"),
+                parent,
+                children,
+            ],
+        );
+        tree["tree"] = json!(strategy);
+        node(tree)
+    };
+
+    let mut checked = table(
+        "t1",
+        "Product",
+        vec![
+            primary("c1", "id", "number", Some("increment")),
+            column("c2", "price", "number"),
+            column("c3", "listPrice", "number"),
+            column("c4", "sku", "string"),
+        ],
+    );
+    checked["checks"] = json!([
+        { "id": "k1", "name": "CHK_price_below_list", "expression": "\"price\" <= \"listPrice\"" }
+    ]);
+    checked["columns"][1]["checks"] =
+        json!([{ "id": "k2", "name": null, "expression": "\"price\" >= 0" }]);
+    checked["columns"][3]["checks"] = json!([
+        { "id": "k3", "name": "CHK_sku_format", "expression": "\"sku\" ~ '^[A-Z]{3}-[0-9]+$'" }
+    ]);
+
+    let view = |id: &str, name: &str, db_name: &str, expression: &str, columns: Vec<Value>| {
+        let mut view = table(id, name, columns);
+        view["dbName"] = json!(db_name);
+        view["kind"] = json!("view");
+        view["expression"] = json!(expression);
+        node(view)
+    };
+    let mut contact = column("c4", "email", "string");
+    contact["dbName"] = json!("contact");
+
+    let in_schema = |id: &str, name: &str, schema: Value, mut columns: Vec<Value>| {
+        columns.insert(
+            0,
+            primary(&format!("{}_id", id), "id", "number", Some("increment")),
+        );
+        let mut table = table(id, name, columns);
+        table["schema"] = schema;
+        node(table)
+    };
+
+    let mut ticket_key = primary("c1", "id", "number", Some("identity"));
+    ticket_key["identityGeneration"] = json!("ALWAYS");
+    let mut public_id = column("c2", "publicId", "uuid");
+    public_id["generated"] = json!("uuid");
+    let mut number = column("c3", "number", "number");
+    number["generated"] = json!("identity");
+    number["identityGeneration"] = json!("BY DEFAULT");
+
+    vec![
+        (
+            "scalar columns",
+            json!([node(table(
+                "t1",
+                "Product",
+                vec![
+                    primary("c1", "id", "number", Some("increment")),
+                    column("c2", "name", "string"),
+                    price,
+                    status,
+                    email
+                ]
+            ))]),
+        ),
+        (
+            "dates",
+            json!([node(table(
+                "t1",
+                "Person",
+                vec![
+                    primary("c1", "id", "number", Some("increment")),
+                    column("c2", "birthday", "dateOnly"),
+                    column("c3", "lastSeenAt", "date"),
+                    column("c4", "signedUpAt", "timestamptz"),
+                ]
+            ))]),
+        ),
+        (
+            "natural key",
+            json!([node(table(
+                "t1",
+                "Country",
+                vec![natural_key, column("c2", "name", "string")]
+            ))]),
+        ),
+        (
+            "composite key",
+            json!([node(table(
+                "t1",
+                "Membership",
+                vec![
+                    primary("c1", "userId", "number", None),
+                    primary("c2", "groupId", "number", None),
+                    column("c3", "role", "string")
+                ]
+            ))]),
+        ),
+        (
+            "composite key with relation",
+            json!([
+                node(table(
+                    "t1",
+                    "Enrollment",
+                    vec![
+                        primary("c1", "studentId", "uuid", None),
+                        primary("c2", "courseCode", "string", None),
+                        relation(
+                            "c3",
+                            "grader",
+                            "many-to-one",
+                            ("t2", "Teacher"),
+                            ("c4", "id")
+                        ),
+                    ]
+                )),
+                node(table(
+                    "t2",
+                    "Teacher",
+                    vec![primary("c4", "id", "number", Some("increment"))]
+                ))
+            ]),
+        ),
+        (
+            "generation strategies",
+            json!([
+                node(table(
+                    "t1",
+                    "Counter",
+                    vec![primary("c1", "id", "number", Some("increment"))]
+                )),
+                node(table(
+                    "t2",
+                    "Session",
+                    vec![primary("c2", "id", "uuid", Some("uuid"))]
+                )),
+                node(table("t3", "Event", vec![identity])),
+                node(table(
+                    "t4",
+                    "Ticket",
+                    vec![primary("c4", "id", "number", Some("rowid"))]
+                ))
+            ]),
+        ),
+        ("table indexes", json!([node(indexed)])),
+        ("entity options", json!([node(with_options)])),
+        ("non-ascii names and descriptions", json!([node(menu)])),
+        (
+            "relations",
+            json!([
+                node(table(
+                    "t1",
+                    "User",
+                    vec![
+                        primary("c1", "id", "number", Some("increment")),
+                        relation(
+                            "c2",
+                            "posts",
+                            "one-to-many",
+                            ("t2", "Post"),
+                            ("c4", "author")
+                        ),
+                        relation(
+                            "c3",
+                            "profile",
+                            "one-to-one",
+                            ("t3", "Profile"),
+                            ("c6", "id")
+                        )
+                    ]
+                )),
+                node(table(
+                    "t2",
+                    "Post",
+                    vec![
+                        primary("c1", "id", "uuid", Some("uuid")),
+                        relation("c4", "author", "many-to-one", ("t1", "User"), ("c1", "id")),
+                        relation("c5", "tags", "many-to-many", ("t4", "Tag"), ("c7", "id"))
+                    ]
+                )),
+                node(table(
+                    "t3",
+                    "Profile",
+                    vec![primary("c6", "id", "number", Some("increment"))]
+                )),
+                node(table(
+                    "t4",
+                    "Tag",
+                    vec![primary("c7", "id", "number", Some("increment"))]
+                ))
+            ]),
+        ),
+        (
+            "enums",
+            json!([
+                node(table(
+                    "t1",
+                    "User",
+                    vec![
+                        primary("c1", "id", "number", Some("increment")),
+                        enum_column(
+                            "c2",
+                            "role",
+                            json!(["admin", "in review"]),
+                            json!("user_role")
+                        ),
+                        enum_column("c3", "status", json!(["active", "banned"]), Value::Null),
+                    ]
+                )),
+                node(table(
+                    "t2",
+                    "Invite",
+                    vec![
+                        primary("c4", "id", "number", Some("increment")),
+                        enum_column(
+                            "c5",
+                            "role",
+                            json!(["admin", "in review"]),
+                            json!("user_role")
+                        ),
+                    ]
+                ))
+            ]),
+        ),
+        (
+            "defaults",
+            json!([node(table(
+                "t1",
+                "Setting",
+                vec![
+                    primary("c1", "id", "number", Some("increment")),
+                    with_default("c2", "retries", "number", json!("5"), "literal"),
+                    with_default("c3", "ratio", "float", json!(0.5), "literal"),
+                    with_default("c4", "enabled", "boolean", json!("true"), "literal"),
+                    with_default("c5", "label", "string", json!("say \"hi\""), "literal"),
+                    with_default("c6", "createdAt", "date", json!("now()"), "expression"),
+                    with_default("c7", "code", "string", json!("12"), "literal"),
+                ]
+            ))]),
+        ),
+        ("descriptions", json!([node(invoice)])),
+        (
+            "embeddables",
+            json!([
+                node(table(
+                    "t1",
+                    "Customer",
+                    vec![
+                        primary("c1", "id", "number", Some("increment")),
+                        embedded("c2", "billing", Value::Null),
+                        embedded("c3", "shipping", json!("ship_")),
+                        embedded("c4", "home", json!(false)),
+                    ]
+                )),
+                node(address)
+            ]),
+        ),
+        (
+            "inheritance",
+            json!([
+                node(post),
+                node(base),
+                node(photo),
+                node(content),
+                node(video)
+            ]),
+        ),
+        (
+            "trees",
+            json!([
+                tree("t1", "Category", "closure-table"),
+                tree("t2", "Comment", "materialized-path"),
+                tree("t3", "Region", "nested-set")
+            ]),
+        ),
+        ("checks", json!([node(checked)])),
+        (
+            "views",
+            json!([
+                node(table(
+                    "t1",
+                    "User",
+                    vec![
+                        primary("c1", "id", "number", Some("increment")),
+                        column("c2", "email", "string")
+                    ]
+                )),
+                view(
+                    "t2",
+                    "ActiveUser",
+                    "active_users",
+                    "SELECT u.id, u.email AS contact\nFROM user u\nWHERE u.active",
+                    vec![column("c3", "id", "number"), contact]
+                ),
+                view(
+                    "t3",
+                    "UserCount",
+                    "user_count",
+                    "SELECT count(*) AS total FROM user",
+                    vec![column("c5", "total", "number")]
+                )
+            ]),
+        ),
+        (
+            "schemas",
+            json!([
+                in_schema(
+                    "t1",
+                    "User",
+                    json!("auth"),
+                    vec![relation(
+                        "c1",
+                        "plans",
+                        "many-to-many",
+                        ("t2", "Plan"),
+                        ("t2_id", "id")
+                    )]
+                ),
+                in_schema("t2", "Plan", json!("billing"), vec![]),
+                in_schema(
+                    "t3",
+                    "Invoice",
+                    json!("billing"),
+                    vec![relation(
+                        "c2",
+                        "user",
+                        "many-to-one",
+                        ("t1", "User"),
+                        ("t1_id", "id")
+                    )]
+                ),
+                in_schema("t4", "Setting", Value::Null, vec![])
+            ]),
+        ),
+        (
+            "generated columns",
+            json!([node(table(
+                "t1",
+                "Ticket",
+                vec![
+                    ticket_key,
+                    public_id,
+                    number,
+                    column("c4", "title", "string")
+                ]
+            ))]),
+        ),
+        (
+            "inflected names",
+            json!([
+                node(table(
+                    "t1",
+                    "Post",
+                    vec![
+                        primary("c1", "id", "number", Some("increment")),
+                        relation(
+                            "c2",
+                            "canvas",
+                            "many-to-one",
+                            ("t2", "Canvas"),
+                            ("c4", "id")
+                        ),
+                        relation(
+                            "c3",
+                            "requests",
+                            "many-to-many",
+                            ("t3", "HTTPRequest"),
+                            ("c5", "id")
+                        ),
+                        relation("c6", "person", "many-to-one", ("t4", "Person"), ("", "")),
+                    ]
+                )),
+                node(table(
+                    "t2",
+                    "Canvas",
+                    vec![primary("c4", "id", "number", Some("increment"))]
+                )),
+                node(table(
+                    "t3",
+                    "HTTPRequest",
+                    vec![primary("c5", "id", "number", Some("increment"))]
+                )),
+                // inverse sides left empty are named after the class.
+                node(table(
+                    "t4",
+                    "Person",
+                    vec![
+                        primary("c7", "id", "number", Some("increment")),
+                        relation("c8", "posts", "one-to-many", ("t1", "Post"), ("", "")),
+                    ]
+                ))
+            ]),
+        ),
+    ]
+}
+
+/**
+The corpus case called `name`.
+*/
+pub fn case(name: &str) -> Value {
+    corpus()
+        .into_iter()
+        .find(|(case, _)| *case == name)
+        .map(|(_, nodes)| nodes)
+        .unwrap_or_else(|| panic!("no corpus case {:?}", name))
+}
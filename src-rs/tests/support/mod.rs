@@ -0,0 +1,792 @@
+//! Test support: a small TypeScript parser producing the ESTree JSON that
+//! `@typescript-eslint/parser` hands to `convert_from_typeorm` in the app.
+//!
+//! Only the subset of TypeScript the generators emit is understood: imports,
+//! (exported, abstract) decorated classes with properties and methods, enums,
+//! type aliases and literal-ish expressions. Everything else is skipped.
+//!
+//! Like the JS parser, ranges and columns count UTF-16 code units, not bytes.
+
+#![allow(dead_code)]
+
+pub mod corpus;
+
+use serde_json::{json, Value};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    Ident,
+    Str,
+    Num,
+    Template,
+    Punct,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: Kind,
+    text: String,
+    start: usize,
+    end: usize,
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = source[i..].chars().next().unwrap();
+        if c.is_whitespace() {
+            i += c.len_utf8();
+        } else if source[i..].starts_with("//") {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+        } else if source[i..].starts_with("/*") {
+            i = source[i + 2..]
+                .find("*/")
+                .map(|end| i + end + 4)
+                .unwrap_or(bytes.len());
+        } else if c == '"' || c == '\'' || c == '`' {
+            let start = i;
+            let mut text = String::new();
+            i += 1;
+            while i < bytes.len() && bytes[i] as char != c {
+                if bytes[i] == b'\\' && i + 1 < bytes.len() {
+                    i += 1;
+                    text.push(match bytes[i] as char {
+                        'n' => '\n',
+                        't' => '\t',
+                        other => other,
+                    });
+                    i += 1;
+                } else {
+                    let ch = source[i..].chars().next().unwrap();
+                    text.push(ch);
+                    i += ch.len_utf8();
+                }
+            }
+            i += 1;
+            let kind = if c == '`' { Kind::Template } else { Kind::Str };
+            tokens.push(Token {
+                kind,
+                text,
+                start,
+                end: i,
+            });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < bytes.len() && ((bytes[i] as char).is_ascii_digit() || bytes[i] == b'.') {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: Kind::Num,
+                text: source[start..i].to_string(),
+                start,
+                end: i,
+            });
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let start = i;
+            for ch in source[i..].chars() {
+                if !(ch.is_alphanumeric() || ch == '_' || ch == '$') {
+                    break;
+                }
+                i += ch.len_utf8();
+            }
+            tokens.push(Token {
+                kind: Kind::Ident,
+                text: source[start..i].to_string(),
+                start,
+                end: i,
+            });
+        } else {
+            let start = i;
+            let text = if source[i..].starts_with("=>") || source[i..].starts_with("...") {
+                if source[i..].starts_with("=>") {
+                    "=>"
+                } else {
+                    "..."
+                }
+            } else {
+                &source[i..i + c.len_utf8()]
+            };
+            i += text.len();
+            tokens.push(Token {
+                kind: Kind::Punct,
+                text: text.to_string(),
+                start,
+                end: i,
+            });
+        }
+    }
+    tokens
+}
+
+/**
+UTF-16 code unit offset of the byte `offset` in `source`.
+*/
+fn utf16_offset(source: &str, offset: usize) -> usize {
+    source[..offset].encode_utf16().count()
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+/**
+Parse TypeScript `source` into an ESTree `Program`.
+*/
+pub fn parse_program(source: &str) -> Value {
+    let mut parser = Parser {
+        source,
+        tokens: tokenize(source),
+        pos: 0,
+    };
+    let mut body = Vec::new();
+    while parser.pos < parser.tokens.len() {
+        if let Some(statement) = parser.statement() {
+            body.push(statement);
+        }
+    }
+    json!({ "type": "Program", "sourceType": "module", "body": body, "range": [0, utf16_offset(source, source.len())] })
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_text(&self, offset: usize) -> &str {
+        self.tokens
+            .get(self.pos + offset)
+            .map(|t| t.text.as_str())
+            .unwrap_or("")
+    }
+
+    fn is(&self, text: &str) -> bool {
+        self.peek()
+            .map(|t| t.text == text && t.kind != Kind::Str)
+            .unwrap_or(false)
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.is(text) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn start(&self) -> usize {
+        self.peek().map(|t| t.start).unwrap_or(self.source.len())
+    }
+
+    fn end(&self) -> usize {
+        self.tokens[self.pos - 1].end
+    }
+
+    fn location(&self, start: usize, end: usize) -> (Value, Value) {
+        let position = |offset: usize| {
+            let before = &self.source[..offset];
+            let line = before.matches('\n').count() + 1;
+            let line_start = before.rfind('\n').map(|n| n + 1).unwrap_or(0);
+            let column = before[line_start..].encode_utf16().count();
+            json!({ "line": line, "column": column })
+        };
+        (
+            json!([
+                utf16_offset(self.source, start),
+                utf16_offset(self.source, end)
+            ]),
+            json!({ "start": position(start), "end": position(end) }),
+        )
+    }
+
+    fn node(&self, mut node: Value, start: usize) -> Value {
+        let (range, loc) = self.location(start, self.end());
+        node["range"] = range;
+        node["loc"] = loc;
+        node
+    }
+
+    /// Skip a balanced `(...)`, `{...}`, `[...]` or `<...>` group.
+    fn skip_group(&mut self) {
+        let open = self.next().text;
+        let close = match open.as_str() {
+            "(" => ")",
+            "{" => "}",
+            "[" => "]",
+            _ => ">",
+        };
+        let mut depth = 1;
+        while depth > 0 && self.pos < self.tokens.len() {
+            let token = self.next();
+            if token.kind != Kind::Punct {
+                continue;
+            }
+            if token.text == open {
+                depth += 1;
+            } else if token.text == close {
+                depth -= 1;
+            }
+        }
+    }
+
+    /// Skip to the end of the current statement.
+    fn skip_statement(&mut self) {
+        while self.pos < self.tokens.len() {
+            if self.is("(") || self.is("{") || self.is("[") {
+                self.skip_group();
+                if self.tokens[self.pos - 1].text == "}" && !self.is(";") {
+                    return;
+                }
+                continue;
+            }
+            if self.eat(";") {
+                return;
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn statement(&mut self) -> Option<Value> {
+        let start = self.start();
+        if self.eat("import") {
+            return Some(self.import_declaration(start));
+        }
+        if self.eat("export") {
+            self.eat("default");
+            let declaration = self.statement()?;
+            return Some(self.node(
+                json!({ "type": "ExportNamedDeclaration", "declaration": declaration }),
+                start,
+            ));
+        }
+        if self.is("@") {
+            // `@Entity() export class X {}`: the decorators belong to the exported class.
+            let decorators = self.decorators();
+            let exported = self.eat("export");
+            self.eat("default");
+            let mut class = self.class_declaration();
+            class["decorators"] = json!(decorators);
+            let (range, loc) = self.location(start, self.end());
+            class["range"] = range;
+            class["loc"] = loc;
+            if !exported {
+                return Some(class);
+            }
+            return Some(self.node(
+                json!({ "type": "ExportNamedDeclaration", "declaration": class }),
+                start,
+            ));
+        }
+        if self.is("class") || self.is("abstract") {
+            return Some(self.class_declaration());
+        }
+        if self.eat("enum") {
+            return Some(self.enum_declaration(start));
+        }
+        if self.is("type") && self.peek_text(2) == "=" {
+            self.pos += 1;
+            let id = self.identifier();
+            self.eat("=");
+            let type_annotation = self.type_annotation();
+            self.eat(";");
+            return Some(self.node(
+                json!({ "type": "TSTypeAliasDeclaration", "id": id, "typeAnnotation": type_annotation }),
+                start,
+            ));
+        }
+        if self.is("const") || self.is("let") {
+            let kind = self.next().text;
+            let id = self.identifier();
+            if self.eat(":") {
+                self.type_annotation();
+            }
+            let init = if self.eat("=") {
+                self.expression()
+            } else {
+                Value::Null
+            };
+            self.eat(";");
+            return Some(self.node(
+                json!({
+                    "type": "VariableDeclaration",
+                    "kind": kind,
+                    "declarations": [{ "type": "VariableDeclarator", "id": id, "init": init }]
+                }),
+                start,
+            ));
+        }
+        self.skip_statement();
+        None
+    }
+
+    fn import_declaration(&mut self, start: usize) -> Value {
+        let mut specifiers = Vec::new();
+        if self.eat("{") {
+            while !self.eat("}") {
+                let imported = self.identifier();
+                let local = if self.eat("as") {
+                    self.identifier()
+                } else {
+                    imported.clone()
+                };
+                specifiers.push(
+                    json!({ "type": "ImportSpecifier", "imported": imported, "local": local }),
+                );
+                self.eat(",");
+            }
+        }
+        while !self.is("from") && self.pos < self.tokens.len() {
+            self.pos += 1;
+        }
+        self.eat("from");
+        let source = self.expression();
+        self.eat(";");
+        self.node(
+            json!({ "type": "ImportDeclaration", "specifiers": specifiers, "source": source }),
+            start,
+        )
+    }
+
+    fn enum_declaration(&mut self, start: usize) -> Value {
+        let id = self.identifier();
+        let mut members = Vec::new();
+        self.eat("{");
+        while !self.eat("}") {
+            let member_start = self.start();
+            let member_id = if self.peek().map(|t| t.kind == Kind::Str).unwrap_or(false) {
+                self.expression()
+            } else {
+                self.identifier()
+            };
+            let initializer = if self.eat("=") {
+                self.expression()
+            } else {
+                Value::Null
+            };
+            members.push(self.node(
+                json!({ "type": "TSEnumMember", "id": member_id, "initializer": initializer }),
+                member_start,
+            ));
+            self.eat(",");
+        }
+        self.node(
+            json!({ "type": "TSEnumDeclaration", "id": id, "members": members }),
+            start,
+        )
+    }
+
+    fn decorators(&mut self) -> Vec<Value> {
+        let mut decorators = Vec::new();
+        while self.is("@") {
+            let start = self.start();
+            self.pos += 1;
+            let expression = self.expression();
+            decorators.push(self.node(
+                json!({ "type": "Decorator", "expression": expression }),
+                start,
+            ));
+        }
+        decorators
+    }
+
+    fn class_declaration(&mut self) -> Value {
+        let start = self.start();
+        let decorators = self.decorators();
+        let is_abstract = self.eat("abstract");
+        self.eat("class");
+        let id = self.identifier();
+        if self.is("<") {
+            self.skip_group();
+        }
+        let super_class = if self.eat("extends") {
+            let super_class = self.expression();
+            if self.is("<") {
+                self.skip_group();
+            }
+            super_class
+        } else {
+            Value::Null
+        };
+        if self.eat("implements") {
+            while !self.is("{") {
+                self.pos += 1;
+            }
+        }
+
+        let body_start = self.start();
+        self.eat("{");
+        let mut members = Vec::new();
+        while !self.eat("}") && self.pos < self.tokens.len() {
+            if self.eat(";") {
+                continue;
+            }
+            members.push(self.class_member());
+        }
+        let body = self.node(json!({ "type": "ClassBody", "body": members }), body_start);
+
+        self.node(
+            json!({
+                "type": "ClassDeclaration",
+                "id": id,
+                "abstract": is_abstract,
+                "superClass": super_class,
+                "decorators": decorators,
+                "body": body
+            }),
+            start,
+        )
+    }
+
+    fn class_member(&mut self) -> Value {
+        let start = self.start();
+        let decorators = self.decorators();
+        let mut is_static = false;
+        let mut kind = "method";
+        loop {
+            let modifier = self.peek_text(0).to_string();
+            let next = self.peek_text(1).to_string();
+            let is_modifier = [
+                "public",
+                "private",
+                "protected",
+                "readonly",
+                "static",
+                "abstract",
+                "declare",
+                "async",
+                "override",
+                "get",
+                "set",
+            ]
+            .contains(&modifier.as_str());
+            if !is_modifier || ["(", ":", ";", "?", "!", "=", "<"].contains(&next.as_str()) {
+                break;
+            }
+            if modifier == "static" {
+                is_static = true;
+            }
+            if modifier == "get" || modifier == "set" {
+                kind = if modifier == "get" { "get" } else { "set" };
+            }
+            self.pos += 1;
+        }
+
+        let key = self.identifier();
+        let optional = self.eat("?");
+        self.eat("!");
+
+        if self.is("(") || self.is("<") {
+            if self.is("<") {
+                self.skip_group();
+            }
+            self.skip_group();
+            if self.eat(":") {
+                self.type_annotation();
+            }
+            if self.is("{") {
+                self.skip_group();
+            } else {
+                self.eat(";");
+            }
+            return self.node(
+                json!({
+                    "type": "MethodDefinition",
+                    "kind": if key["name"] == "constructor" { "constructor" } else { kind },
+                    "static": is_static,
+                    "key": key,
+                    "decorators": decorators
+                }),
+                start,
+            );
+        }
+
+        let type_annotation = if self.is(":") {
+            let annotation_start = self.start();
+            self.pos += 1;
+            let annotation = self.type_annotation();
+            self.node(
+                json!({ "type": "TSTypeAnnotation", "typeAnnotation": annotation }),
+                annotation_start,
+            )
+        } else {
+            Value::Null
+        };
+        let value = if self.eat("=") {
+            self.expression()
+        } else {
+            Value::Null
+        };
+        self.eat(";");
+
+        self.node(
+            json!({
+                "type": "PropertyDefinition",
+                "static": is_static,
+                "optional": optional,
+                "key": key,
+                "value": value,
+                "typeAnnotation": type_annotation,
+                "decorators": decorators
+            }),
+            start,
+        )
+    }
+
+    fn identifier(&mut self) -> Value {
+        let start = self.start();
+        let token = self.next();
+        self.node(json!({ "type": "Identifier", "name": token.text }), start)
+    }
+
+    fn type_annotation(&mut self) -> Value {
+        let start = self.start();
+        let mut types = vec![self.single_type()];
+        while self.eat("|") {
+            types.push(self.single_type());
+        }
+        if types.len() == 1 {
+            types.remove(0)
+        } else {
+            self.node(json!({ "type": "TSUnionType", "types": types }), start)
+        }
+    }
+
+    fn single_type(&mut self) -> Value {
+        let start = self.start();
+        let token = self.next();
+        let mut annotation = match (token.kind.clone(), token.text.as_str()) {
+            (Kind::Str, _) | (Kind::Num, _) => {
+                self.pos -= 1;
+                let literal = self.expression();
+                self.node(
+                    json!({ "type": "TSLiteralType", "literal": literal }),
+                    start,
+                )
+            }
+            (_, "string") => self.node(json!({ "type": "TSStringKeyword" }), start),
+            (_, "number") => self.node(json!({ "type": "TSNumberKeyword" }), start),
+            (_, "boolean") => self.node(json!({ "type": "TSBooleanKeyword" }), start),
+            (_, "bigint") => self.node(json!({ "type": "TSBigIntKeyword" }), start),
+            (_, "any") => self.node(json!({ "type": "TSAnyKeyword" }), start),
+            (_, "unknown") => self.node(json!({ "type": "TSUnknownKeyword" }), start),
+            (_, "null") => self.node(json!({ "type": "TSNullKeyword" }), start),
+            (_, "undefined") => self.node(json!({ "type": "TSUndefinedKeyword" }), start),
+            (_, "{") => {
+                self.pos -= 1;
+                self.skip_group();
+                self.node(json!({ "type": "TSTypeLiteral", "members": [] }), start)
+            }
+            (_, name) => {
+                let mut type_name = name.to_string();
+                while self.eat(".") {
+                    type_name = format!("{}.{}", type_name, self.next().text);
+                }
+                let mut reference = json!({
+                    "type": "TSTypeReference",
+                    "typeName": { "type": "Identifier", "name": type_name }
+                });
+                if self.is("<") {
+                    self.pos += 1;
+                    let mut params = vec![self.type_annotation()];
+                    while self.eat(",") {
+                        params.push(self.type_annotation());
+                    }
+                    self.eat(">");
+                    reference["typeArguments"] = json!({ "params": params });
+                }
+                self.node(reference, start)
+            }
+        };
+        while self.is("[") && self.peek_text(1) == "]" {
+            self.pos += 2;
+            annotation = self.node(
+                json!({ "type": "TSArrayType", "elementType": annotation }),
+                start,
+            );
+        }
+        annotation
+    }
+
+    fn expression(&mut self) -> Value {
+        let start = self.start();
+        let mut expression = self.primary();
+        loop {
+            if self.eat(".") {
+                let property = self.identifier();
+                expression = self.node(
+                    json!({ "type": "MemberExpression", "object": expression, "property": property, "computed": false }),
+                    start,
+                );
+            } else if self.is("(") {
+                self.pos += 1;
+                let mut arguments = Vec::new();
+                while !self.eat(")") {
+                    arguments.push(self.expression());
+                    self.eat(",");
+                }
+                expression = self.node(
+                    json!({ "type": "CallExpression", "callee": expression, "arguments": arguments }),
+                    start,
+                );
+            } else if self.is("as") {
+                self.pos += 1;
+                self.type_annotation();
+            } else {
+                break;
+            }
+        }
+        expression
+    }
+
+    fn is_arrow_ahead(&self) -> bool {
+        // `(` ... `)` `=>`
+        let mut depth = 0;
+        for (offset, token) in self.tokens[self.pos..].iter().enumerate() {
+            if token.kind != Kind::Punct {
+                continue;
+            }
+            match token.text.as_str() {
+                "(" => depth += 1,
+                ")" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return self.peek_text(offset + 1) == "=>"
+                            || self.peek_text(offset + 1) == ":";
+                    }
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn arrow_function(&mut self, start: usize, params: Vec<Value>) -> Value {
+        if self.eat(":") {
+            self.type_annotation();
+        }
+        self.eat("=>");
+        let body = if self.is("{") {
+            let body_start = self.start();
+            self.skip_group();
+            self.node(json!({ "type": "BlockStatement", "body": [] }), body_start)
+        } else {
+            self.expression()
+        };
+        self.node(
+            json!({ "type": "ArrowFunctionExpression", "params": params, "body": body }),
+            start,
+        )
+    }
+
+    fn primary(&mut self) -> Value {
+        let start = self.start();
+        let token = self.next();
+        match (token.kind, token.text.as_str()) {
+            (Kind::Str, text) => self.node(
+                json!({ "type": "Literal", "value": text, "raw": &self.source[token.start..token.end] }),
+                start,
+            ),
+            (Kind::Template, text) => self.node(
+                json!({
+                    "type": "TemplateLiteral",
+                    "quasis": [{ "type": "TemplateElement", "value": { "raw": text, "cooked": text }, "tail": true }],
+                    "expressions": []
+                }),
+                start,
+            ),
+            (Kind::Num, text) => {
+                let value: Value = if text.contains('.') {
+                    json!(text.parse::<f64>().unwrap_or(0.0))
+                } else {
+                    json!(text.parse::<i64>().unwrap_or(0))
+                };
+                self.node(json!({ "type": "Literal", "value": value, "raw": text }), start)
+            }
+            (Kind::Punct, "-") => {
+                let argument = self.primary();
+                self.node(
+                    json!({ "type": "UnaryExpression", "operator": "-", "prefix": true, "argument": argument }),
+                    start,
+                )
+            }
+            (Kind::Punct, "[") => {
+                let mut elements = Vec::new();
+                while !self.eat("]") {
+                    elements.push(self.expression());
+                    self.eat(",");
+                }
+                self.node(json!({ "type": "ArrayExpression", "elements": elements }), start)
+            }
+            (Kind::Punct, "{") => {
+                let mut properties = Vec::new();
+                while !self.eat("}") {
+                    let property_start = self.start();
+                    let key = if self.peek().map(|t| t.kind == Kind::Str).unwrap_or(false) {
+                        self.primary()
+                    } else {
+                        self.identifier()
+                    };
+                    let value = if self.eat(":") { self.expression() } else { key.clone() };
+                    properties.push(self.node(
+                        json!({ "type": "Property", "key": key, "value": value, "kind": "init" }),
+                        property_start,
+                    ));
+                    self.eat(",");
+                }
+                self.node(json!({ "type": "ObjectExpression", "properties": properties }), start)
+            }
+            (Kind::Punct, "(") => {
+                self.pos -= 1;
+                if self.is_arrow_ahead() {
+                    self.pos += 1;
+                    let mut params = Vec::new();
+                    while !self.eat(")") {
+                        params.push(self.identifier());
+                        if self.eat(":") {
+                            self.type_annotation();
+                        }
+                        self.eat(",");
+                    }
+                    self.arrow_function(start, params)
+                } else {
+                    self.pos += 1;
+                    let expression = self.expression();
+                    self.eat(")");
+                    expression
+                }
+            }
+            (Kind::Ident, "true") | (Kind::Ident, "false") => self.node(
+                json!({ "type": "Literal", "value": token.text == "true", "raw": token.text }),
+                start,
+            ),
+            (Kind::Ident, "null") => {
+                self.node(json!({ "type": "Literal", "value": null, "raw": "null" }), start)
+            }
+            (Kind::Ident, "new") => {
+                let callee = self.expression();
+                self.node(json!({ "type": "NewExpression", "callee": callee }), start)
+            }
+            (Kind::Ident, name) => {
+                let identifier = self.node(json!({ "type": "Identifier", "name": name }), start);
+                if self.is("=>") {
+                    self.arrow_function(start, vec![identifier])
+                } else {
+                    identifier
+                }
+            }
+            (_, text) => self.node(json!({ "type": "Unknown", "raw": text }), start),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Data types are written as the native column types of the `dialect`, and read back.
+
+mod support;
+
+use serde_json::{json, Value};
+use src_rs::{convert_from_typeorm, convert_to_typeorm, normalize};
+
+fn schema() -> Value {
+    let column = |id: &str, name: &str, data_type: &str| {
+        json!({ "id": id, "name": name, "dbName": name, "dataType": data_type, "primaryKey": false,
+                "foreignKey": null })
+    };
+    json!([{ "id": "t1", "type": "table", "data": { "id": "t1", "name": "Event", "dbName": "events", "columns": [
+        { "id": "c1", "name": "id", "dataType": "number", "primaryKey": true, "generated": "increment", "foreignKey": null },
+        column("c2", "title", "string"),
+        column("c3", "attendees", "number"),
+        column("c4", "score", "float"),
+        column("c5", "startsAt", "date"),
+        column("c6", "payload", "json"),
+        column("c7", "area", "geometry"),
+    ]}}])
+}
+
+fn precise_schema() -> Value {
+    let column = |id: &str, name: &str, data_type: &str| {
+        json!({ "id": id, "name": name, "dbName": name, "dataType": data_type, "primaryKey": false,
+                "foreignKey": null })
+    };
+    json!([{ "id": "t1", "type": "table", "data": { "id": "t1", "name": "Payment", "dbName": "payments", "columns": [
+        { "id": "c1", "name": "id", "dataType": "bigint", "primaryKey": true, "generated": "increment", "foreignKey": null },
+        column("c2", "amount", "decimal"),
+        column("c3", "settled", "boolean"),
+        column("c4", "memo", "text"),
+        column("c5", "receipt", "binary"),
+        column("c6", "cutoff", "time"),
+        column("c7", "paidAt", "timestamptz"),
+    ]}}])
+}
+
+fn generate(dialect: &str) -> String {
+    let options = json!({ "dialect": dialect }).to_string();
+    convert_to_typeorm(&schema().to_string(), Some(options))
+}
+
+#[test]
+fn native_types_follow_the_dialect() {
+    for (dialect, expected) in [
+        (
+            "postgres",
+            [
+                "varchar",
+                "integer",
+                "double precision",
+                "timestamp",
+                "json",
+            ],
+        ),
+        ("mysql", ["varchar", "int", "double", "datetime", "json"]),
+        ("sqlite", ["varchar", "integer", "real", "datetime", "text"]),
+        (
+            "mssql",
+            ["nvarchar", "int", "float", "datetime2", "nvarchar"],
+        ),
+    ] {
+        let code = generate(dialect);
+        let names = ["title", "attendees", "score", "startsAt", "payload"];
+        for (name, native) in names.iter().zip(expected) {
+            let column = format!("@Column({{ name: \"{}\", type: \"{}\" }})", name, native);
+            assert!(code.contains(&column), "missing {:?} in:\n{}", column, code);
+        }
+    }
+}
+
+#[test]
+fn properties_are_typed_by_data_type() {
+    let code = generate("mysql");
+
+    for expected in [
+        "    title: string;",
+        "    attendees: number;",
+        "    score: number;",
+        "    startsAt: Date;",
+        "    payload: any;",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+}
+
+#[test]
+fn unknown_types_are_typed_unknown() {
+    let code = generate("postgres");
+
+    assert!(
+        code.contains("@Column({ name: \"area\", type: \"geometry\" })\n    area: unknown;"),
+        "{}",
+        code
+    );
+}
+
+// sqlite and mssql store json as text, which reads back as a text column.
+#[test]
+fn types_round_trip_per_dialect() {
+    let input = schema().to_string();
+    for dialect in ["postgres", "mysql"] {
+        let options = json!({ "dialect": dialect }).to_string();
+        let code = convert_to_typeorm(&input, Some(options.clone()));
+        let program = support::parse_program(&code).to_string();
+        let parsed = convert_from_typeorm(&program, Some(options));
+
+        assert_eq!(
+            normalize(&parsed, None),
+            normalize(&input, None),
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn precise_types_are_emitted() {
+    let options = json!({ "dialect": "postgres" }).to_string();
+    let code = convert_to_typeorm(&precise_schema().to_string(), Some(options));
+
+    for expected in [
+        "    @PrimaryGeneratedColumn({ type: \"bigint\" })\n    id: string;",
+        "    @Column({ name: \"amount\", type: \"numeric\" })\n    amount: string;",
+        "    @Column({ name: \"settled\", type: \"boolean\" })\n    settled: boolean;",
+        "    @Column({ name: \"memo\", type: \"text\" })\n    memo: string;",
+        "    @Column({ name: \"receipt\", type: \"bytea\" })\n    receipt: Buffer;",
+        "    @Column({ name: \"cutoff\", type: \"time\" })\n    cutoff: string;",
+        "    @Column({ name: \"paidAt\", type: \"timestamp with time zone\" })\n    paidAt: Date;",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+}
+
+// sqlite has no time zone aware type, its datetime reads back as a date.
+#[test]
+fn precise_types_round_trip_per_dialect() {
+    let input = precise_schema().to_string();
+    for dialect in ["postgres", "mysql", "mssql"] {
+        let options = json!({ "dialect": dialect }).to_string();
+        let code = convert_to_typeorm(&input, Some(options.clone()));
+        let program = support::parse_program(&code).to_string();
+        let parsed = convert_from_typeorm(&program, Some(options));
+
+        assert_eq!(
+            normalize(&parsed, None),
+            normalize(&input, None),
+            "{}",
+            code
+        );
+    }
+}
+
+// a Postgres `date` holds no time, it must not read back as the `timestamp` of a `date`.
+#[test]
+fn date_only_columns_keep_their_type() {
+    let code = convert_to_typeorm(&support::corpus::case("dates").to_string(), None);
+
+    assert!(
+        code.contains("@Column({ name: \"birthday\", type: \"date\" })\n    birthday: string;"),
+        "{}",
+        code
+    );
+}
@@ -0,0 +1,248 @@
+//! Plain TS interfaces and create / update DTO types, or class-validator DTO classes.
+
+mod support;
+
+use serde_json::{json, Value};
+use src_rs::convert_to_typescript;
+use support::corpus::case;
+
+fn schema() -> Value {
+    let column = |id: &str, name: &str, data_type: &str| {
+        json!({ "id": id, "name": name, "dbName": name, "dataType": data_type, "primaryKey": false,
+                "foreignKey": null })
+    };
+    let relation = |id: &str, name: &str, join_type: &str, target: (&str, &str)| {
+        json!({ "id": id, "name": name, "dbName": format!("{}_id", name), "dataType": "", "primaryKey": false,
+                "foreignKey": {
+                    "type": join_type,
+                    "target": { "table": target.0, "tableName": target.1, "column": "id", "columnName": "id" },
+                    "through": null, "onDelete": "CASCADE", "onUpdate": "CASCADE"
+                } })
+    };
+    let mut bio = column("c3", "bio", "text");
+    bio["nullable"] = json!(true);
+    bio["description"] = json!("Shown on the profile page.");
+    let mut role = column("c4", "role", "enum");
+    role["enum"] = json!(["admin", "member"]);
+    role["enumName"] = json!("user_role");
+    role["defaultValue"] = json!("member");
+    let mut created_at = column("c5", "createdAt", "date");
+    created_at["defaultValue"] = json!("now()");
+    created_at["defaultKind"] = json!("expression");
+    let mut editor = relation("c9", "editor", "many-to-one", ("t1", "User"));
+    editor["nullable"] = json!(true);
+    json!([
+        { "id": "t1", "type": "table", "data": { "id": "t1", "name": "User", "dbName": "users", "columns": [
+            { "id": "c1", "name": "id", "dataType": "number", "primaryKey": true, "generated": "increment", "foreignKey": null },
+            column("c2", "email", "string"),
+            bio,
+            role,
+            created_at,
+            relation("c6", "posts", "one-to-many", ("t2", "Post")),
+            { "id": "c13", "name": "address", "dataType": "", "primaryKey": false, "foreignKey": null,
+              "embedded": { "table": "t5", "tableName": "Address", "prefix": null } },
+        ]}},
+        { "id": "t2", "type": "table", "data": { "id": "t2", "name": "Post", "dbName": "posts", "columns": [
+            { "id": "c7", "name": "id", "dataType": "uuid", "primaryKey": true, "generated": "uuid", "foreignKey": null },
+            relation("c8", "author", "many-to-one", ("t1", "User")),
+            editor,
+            column("c10", "body", "text"),
+            column("c15", "updatedAt", "date"),
+        ]}},
+        { "id": "t3", "type": "table", "data": { "id": "t3", "name": "Content", "dbName": "content",
+            "kind": "abstract", "columns": [
+            column("c11", "title", "string"),
+        ]}},
+        { "id": "t4", "type": "table", "data": { "id": "t4", "name": "Page", "dbName": "pages",
+            "extends": { "table": "t3", "tableName": "Content", "strategy": "class-table", "discriminator": null },
+            "columns": [
+            { "id": "c12", "name": "slug", "dataType": "string", "primaryKey": true, "generated": null, "foreignKey": null },
+        ]}},
+        { "id": "t5", "type": "table", "data": { "id": "t5", "name": "Address", "dbName": "address",
+            "kind": "embeddable", "columns": [
+            { "id": "c14", "name": "street", "dbName": "street", "dataType": "string", "length": 80, "primaryKey": false, "foreignKey": null },
+        ]}},
+    ])
+}
+
+#[test]
+fn interfaces_are_emitted() {
+    let code = convert_to_typescript(&schema().to_string(), None);
+
+    for expected in [
+        "export enum UserRole {\n    ADMIN = \"admin\",\n    MEMBER = \"member\",\n}",
+        "export interface User {\n    id: number;\n    email: string;\n    /** Shown on the profile page. */\n    bio: string | null;\n    role: UserRole;\n    createdAt: Date;\n    posts: Post[];\n    address: Address;\n}",
+        "export interface Post {\n    id: string;\n    author: User;\n    authorId: number;\n    editor: User | null;\n    editorId: number | null;\n    body: string;\n    updatedAt: Date;\n}",
+        "export interface Content {\n    title: string;\n}",
+        "export interface Page extends Content {\n    slug: string;\n}",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+}
+
+#[test]
+fn dtos_leave_out_generated_values() {
+    let code = convert_to_typescript(&schema().to_string(), None);
+
+    for expected in [
+        "export interface CreateUserDto {\n    email: string;\n    bio?: string | null;\n    role?: UserRole;\n    address: Address;\n}",
+        "export type UpdateUserDto = Partial<CreateUserDto>;",
+        "export interface CreatePostDto {\n    authorId: number;\n    editorId?: number | null;\n    body: string;\n    updatedAt: Date;\n}",
+        "export interface CreatePageDto {\n    title: string;\n    slug: string;\n}",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+    // timestamps are told apart by their default, not their name.
+    assert!(!code.contains("createdAt?"), "{}", code);
+    // base classes have no rows of their own.
+    assert!(!code.contains("CreateContentDto"), "{}", code);
+}
+
+#[test]
+fn enums_can_be_unions() {
+    let options = json!({ "enumStyle": "union" }).to_string();
+    let code = convert_to_typescript(&schema().to_string(), Some(options));
+
+    assert!(
+        code.contains("export type UserRole = \"admin\" | \"member\";"),
+        "{}",
+        code
+    );
+}
+
+#[test]
+fn dto_classes_are_validated() {
+    let options = json!({ "dtoStyle": "class-validator" }).to_string();
+    let code = convert_to_typescript(&schema().to_string(), Some(options));
+
+    for expected in [
+        "import { PartialType } from \"@nestjs/mapped-types\";\nimport { Type } from \"class-transformer\";\nimport { IsDateString, IsEnum, IsInt, IsOptional, IsString, MaxLength, ValidateNested } from \"class-validator\";\n\n",
+        "export class CreateUserDto {\n    @IsString()\n    @MaxLength(255)\n    email: string;\n\n    @IsOptional()\n    @IsString()\n    bio?: string | null;\n\n    @IsOptional()\n    @IsEnum(UserRole)\n    role?: UserRole;\n\n    @ValidateNested()\n    @Type(() => AddressDto)\n    address: AddressDto;\n}",
+        "export class UpdateUserDto extends PartialType(CreateUserDto) {}",
+        "export class CreatePostDto {\n    @IsInt()\n    authorId: number;\n\n    @IsOptional()\n    @IsInt()\n    editorId?: number | null;\n\n    @IsString()\n    body: string;\n\n    @IsDateString()\n    updatedAt: string;\n}",
+        "export class AddressDto {\n    @IsString()\n    @MaxLength(80)\n    street: string;\n}",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+    assert!(
+        !code.contains("CreateUserDto {\n    email: string;"),
+        "{}",
+        code
+    );
+    // `@Type(() => AddressDto)` and the property's metadata use the class when the module loads.
+    assert!(
+        code.find("export class AddressDto") < code.find("export class CreateUserDto"),
+        "{}",
+        code
+    );
+}
+
+#[test]
+fn validators_follow_the_data_type() {
+    let column = |id: &str, name: &str, data_type: &str| {
+        json!({ "id": id, "name": name, "dbName": name, "dataType": data_type, "primaryKey": false,
+                "foreignKey": null })
+    };
+    let mut status = column("c6", "status", "string");
+    status["enum"] = json!(["open", "closed"]);
+    let nodes = json!([{ "id": "t1", "type": "table", "data": { "id": "t1", "name": "Ticket", "dbName": "tickets", "columns": [
+        { "id": "c1", "name": "id", "dataType": "uuid", "primaryKey": true, "generated": "uuid", "foreignKey": null },
+        column("c2", "reporterId", "uuid"),
+        column("c3", "dueAt", "date"),
+        column("c4", "amount", "decimal"),
+        column("c5", "urgent", "boolean"),
+        status,
+    ]}}]);
+    let options = json!({ "dtoStyle": "class-validator", "enumStyle": "union" }).to_string();
+    let code = convert_to_typescript(&nodes.to_string(), Some(options));
+
+    for expected in [
+        "    @IsUUID()\n    reporterId: string;",
+        "    @IsDateString()\n    dueAt: string;",
+        "    @IsDecimal()\n    amount: string;",
+        "    @IsBoolean()\n    urgent: boolean;",
+        "    @IsIn([\"open\", \"closed\"])\n    status: TicketStatus;",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+}
+
+#[test]
+fn simple_checks_are_validated() {
+    let mut nodes = case("checks");
+    let columns = &mut nodes[0]["data"]["columns"];
+    columns[2]["checks"] =
+        json!([{ "id": "k4", "name": null, "expression": "\"listPrice\" <= 1000" }]);
+    columns[3]["checks"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({ "id": "k5", "name": null, "expression": "length(\"sku\") <= 12" }));
+    let mut grade = columns[1].clone();
+    grade["id"] = json!("c5");
+    grade["name"] = json!("grade");
+    grade["dbName"] = json!("grade");
+    grade["dataType"] = json!("string");
+    grade["checks"] =
+        json!([{ "id": "k6", "name": null, "expression": "grade IN ('A', 'B', 'it''s')" }]);
+    columns.as_array_mut().unwrap().push(grade);
+
+    let options = json!({ "dtoStyle": "class-validator" }).to_string();
+    let code = convert_to_typescript(&nodes.to_string(), Some(options));
+
+    for expected in [
+        "    @IsInt()\n    @Min(0)\n    price: number;",
+        "    @IsInt()\n    @Max(1000)\n    listPrice: number;",
+        // the pattern check is left to the database.
+        "    @IsString()\n    @MaxLength(12)\n    sku: string;",
+        "    @IsString()\n    @MaxLength(255)\n    @IsIn([\"A\", \"B\", \"it's\"])\n    grade: string;",
+        "import { IsIn, IsInt, IsString, Max, MaxLength, Min } from \"class-validator\";",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+}
+
+// `Profile` and `Tag` have nothing but a generated key to create them with.
+#[test]
+fn empty_dtos_have_empty_bodies() {
+    let nodes = case("relations").to_string();
+    let code = convert_to_typescript(&nodes, None);
+    assert!(
+        code.contains("export interface CreateProfileDto {}"),
+        "{}",
+        code
+    );
+    assert!(
+        code.contains("export interface CreateTagDto {}"),
+        "{}",
+        code
+    );
+
+    let options = json!({ "dtoStyle": "class-validator" }).to_string();
+    let code = convert_to_typescript(&nodes, Some(options));
+    assert!(code.contains("export class CreateTagDto {}"), "{}", code);
+}
@@ -0,0 +1,134 @@
+//! Zod schemas with inferred types, relations are lazy references.
+
+use serde_json::{json, Value};
+use src_rs::convert_to_zod;
+
+fn schema() -> Value {
+    let column = |id: &str, name: &str, data_type: &str| {
+        json!({ "id": id, "name": name, "dbName": name, "dataType": data_type, "primaryKey": false,
+                "foreignKey": null })
+    };
+    let relation = |id: &str, name: &str, join_type: &str, target: (&str, &str)| {
+        json!({ "id": id, "name": name, "dbName": format!("{}_id", name), "dataType": "", "primaryKey": false,
+                "foreignKey": {
+                    "type": join_type,
+                    "target": { "table": target.0, "tableName": target.1, "column": "id", "columnName": "id" },
+                    "through": null, "onDelete": "CASCADE", "onUpdate": "CASCADE"
+                } })
+    };
+    let mut email = column("c2", "email", "string");
+    email["length"] = json!(120);
+    email["description"] = json!("Login address.");
+    let mut bio = column("c3", "bio", "text");
+    bio["nullable"] = json!(true);
+    let mut role = column("c4", "role", "enum");
+    role["enum"] = json!(["admin", "member"]);
+    role["defaultValue"] = json!("member");
+    let mut created_at = column("c5", "createdAt", "date");
+    created_at["defaultValue"] = json!("now()");
+    created_at["defaultKind"] = json!("expression");
+    let mut editor = relation("c9", "editor", "many-to-one", ("t1", "User"));
+    editor["nullable"] = json!(true);
+    json!([
+        { "id": "t1", "type": "table", "data": { "id": "t1", "name": "User", "dbName": "users", "columns": [
+            { "id": "c1", "name": "id", "dataType": "uuid", "primaryKey": true, "generated": "uuid", "foreignKey": null },
+            email,
+            bio,
+            role,
+            created_at,
+            relation("c6", "posts", "one-to-many", ("t2", "Post")),
+            { "id": "c11", "name": "address", "dataType": "", "primaryKey": false, "foreignKey": null,
+              "embedded": { "table": "t3", "tableName": "Address", "prefix": null } },
+        ]}},
+        { "id": "t2", "type": "table", "data": { "id": "t2", "name": "Post", "dbName": "posts", "columns": [
+            { "id": "c7", "name": "id", "dataType": "number", "primaryKey": true, "generated": "increment", "foreignKey": null },
+            relation("c8", "author", "many-to-one", ("t1", "User")),
+            editor,
+            column("c10", "rating", "float"),
+        ]}},
+        { "id": "t3", "type": "table", "data": { "id": "t3", "name": "Address", "dbName": "address",
+            "kind": "embeddable", "columns": [
+            column("c12", "city", "string"),
+        ]}},
+    ])
+}
+
+#[test]
+fn schemas_are_emitted() {
+    let code = convert_to_zod(&schema().to_string(), None);
+
+    for expected in [
+        "import { z } from \"zod\";",
+        "export const UserSchema = z.object({\n    id: z.string().uuid(),\n    email: z.string().max(120).describe(\"Login address.\"),\n    bio: z.string().nullable(),\n    role: z.enum([\"admin\", \"member\"]).default(\"member\"),\n    createdAt: z.coerce.date().optional(),\n    address: AddressSchema,\n});\nexport type User = z.infer<typeof UserSchema>;",
+        "export const PostSchema = z.object({\n    id: z.number().int(),\n    authorId: z.string().uuid(),\n    editorId: z.string().uuid().nullable(),\n    rating: z.number(),\n});\nexport type Post = z.infer<typeof PostSchema>;",
+        "export const AddressSchema = z.object({\n    city: z.string().max(255),\n});",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+    // embedded schemas are used directly, they are declared first.
+    assert!(
+        code.find("AddressSchema =").unwrap() < code.find("UserSchema =").unwrap(),
+        "{}",
+        code
+    );
+}
+
+#[test]
+fn relations_are_lazy() {
+    let code = convert_to_zod(&schema().to_string(), None);
+
+    for expected in [
+        "export type UserWithRelations = User & {\n    posts?: PostWithRelations[];\n};",
+        "export const UserWithRelationsSchema: z.ZodType<UserWithRelations, z.ZodTypeDef, unknown> = UserSchema.extend({\n    posts: z.lazy(() => z.array(PostWithRelationsSchema)).optional(),\n});",
+        "export type PostWithRelations = Post & {\n    author?: UserWithRelations;\n    editor?: UserWithRelations | null;\n};",
+        "    editor: z.lazy(() => UserWithRelationsSchema).nullable().optional(),",
+    ] {
+        assert!(
+            code.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            code
+        );
+    }
+    assert!(!code.contains("AddressWithRelations"), "{}", code);
+}
+
+#[test]
+fn child_schemas_extend_their_parent() {
+    let nodes = json!([
+        { "id": "t1", "type": "table", "data": { "id": "t1", "name": "Page", "dbName": "pages",
+            "extends": { "table": "t2", "tableName": "Content", "strategy": "class-table", "discriminator": null },
+            "columns": [
+            { "id": "c1", "name": "slug", "dbName": "slug", "dataType": "string", "primaryKey": false, "foreignKey": null },
+        ]}},
+        { "id": "t2", "type": "table", "data": { "id": "t2", "name": "Content", "dbName": "content",
+            "kind": "abstract", "columns": [
+            { "id": "c2", "name": "published", "dbName": "published", "dataType": "boolean", "primaryKey": false,
+              "foreignKey": null, "defaultValue": "false" },
+        ]}},
+    ]);
+    let code = convert_to_zod(&nodes.to_string(), None);
+
+    assert!(
+        code.contains("export const ContentSchema = z.object({\n    published: z.boolean().default(false),\n});"),
+        "{}",
+        code
+    );
+    assert!(
+        code.contains(
+            "export const PageSchema = ContentSchema.extend({\n    slug: z.string().max(255),\n});"
+        ),
+        "{}",
+        code
+    );
+    assert!(
+        code.find("ContentSchema =").unwrap() < code.find("PageSchema =").unwrap(),
+        "{}",
+        code
+    );
+}
@@ -8,7 +8,11 @@ import {
   joinTables,
 } from "@/lib/flow-editors/helpers";
 import { deleteEdges } from "@/lib/flow-editors/nodes";
-import { JoinProps, TableProps } from "@/lib/types/database-types";
+import {
+  CompilerOptions,
+  JoinProps,
+  TableProps,
+} from "@/lib/types/database-types";
 // import { getMidpoint } from "@/lib/utils";
 import { Editor, type Monaco } from "@monaco-editor/react";
 import { parse } from "@typescript-eslint/parser";
@@ -32,6 +36,16 @@ type CodeEditorProps = {
 
 type ESLintProgram = ReturnType<typeof parse>;
 
+// ESTree of `code`, null when it doesn't parse.
+async function parseProgram(code: string): Promise<ESLintProgram | null> {
+  const response = await fetch("api/validate", {
+    method: "POST",
+    body: JSON.stringify({ code }),
+  });
+  const data: { code: number; data?: ESLintProgram } = await response.json();
+  return data.data ?? null;
+}
+
 function CodeEditorComponent({ className, wasmModule }: CodeEditorProps) {
   const [code, setCode] = useState<string>("");
   const { colorTheme } = useContext(AppContext);
@@ -40,6 +54,11 @@ function CodeEditorComponent({ className, wasmModule }: CodeEditorProps) {
 
   const editorRef = useRef<any>(null);
   const editorFocusedRef = useRef<boolean>(false);
+  // last code that parsed, regenerated entities are merged into it.
+  const previousCodeRef = useRef<{
+    source: string;
+    program: ESLintProgram;
+  } | null>(null);
 
   // monaco options.
   const handleEditorDidMount = async (editor: unknown, monaco: Monaco) => {
@@ -64,8 +83,6 @@ function CodeEditorComponent({ className, wasmModule }: CodeEditorProps) {
             return eds;
           });
 
-          code = code.replaceAll("export ", "");
-
           const response = await fetch("api/validate", {
             method: "POST",
             body: JSON.stringify({ code }),
@@ -83,6 +100,8 @@ function CodeEditorComponent({ className, wasmModule }: CodeEditorProps) {
           }
 
           if (data.data) {
+            previousCodeRef.current = { source: code, program: data.data };
+
             const convertedNodes = wasmModule.convert_from_typeorm(
               JSON.stringify(data.data),
             );
@@ -191,18 +210,49 @@ function CodeEditorComponent({ className, wasmModule }: CodeEditorProps) {
 
   const debouncedCompileToORM = useMemo(
     () =>
-      debounce((nodes: Node<TableProps>[]) => {
+      debounce(async (nodes: Node<TableProps>[]) => {
         if (!wasmModule) return;
         try {
           let parsedNodes = cloneDeep(nodes);
           parsedNodes = parsedNodes.map((node) => {
-            // find if foreign keys exist and return early if unecessary.
+            if (node.data.extends) {
+              const parentTable = nodes.find(
+                (target) => target.id === node.data.extends?.table,
+              );
+              node = {
+                ...node,
+                data: {
+                  ...node.data,
+                  extends: parentTable
+                    ? {
+                        ...node.data.extends,
+                        tableName: parentTable.data.name || parentTable.data.id,
+                      }
+                    : null,
+                },
+              };
+            }
+
+            // find if foreign keys or embeds exist and return early if unecessary.
             const foreignKeys = node.data.columns.filter(
-              (col) => !!col.foreignKey,
+              (col) => !!col.foreignKey || !!col.embedded,
             );
             if (foreignKeys.length === 0) return node;
 
             const columns = node.data.columns.map((col) => {
+              if (col.embedded) {
+                const embeddedTable = nodes.find(
+                  (target) => target.id === col.embedded?.table,
+                );
+                if (!embeddedTable) return { ...col, embedded: null };
+                return {
+                  ...col,
+                  embedded: {
+                    ...col.embedded,
+                    tableName: embeddedTable.data.name || embeddedTable.data.id,
+                  },
+                };
+              }
               if (!col.foreignKey?.target) return col;
               const targetTable = nodes.find(
                 (target) => target.id === col.foreignKey?.target?.table,
@@ -227,7 +277,23 @@ function CodeEditorComponent({ className, wasmModule }: CodeEditorProps) {
             return { ...node, data: { ...node.data, columns } };
           });
 
-          setCode(wasmModule.convert_to_typeorm(JSON.stringify(parsedNodes)));
+          const options: CompilerOptions = {};
+          if (previousCodeRef.current) {
+            options.previousSource = previousCodeRef.current.source;
+            options.previousProgram = previousCodeRef.current.program;
+          }
+
+          const generated = wasmModule.convert_to_typeorm(
+            JSON.stringify(parsedNodes),
+            JSON.stringify(options),
+          );
+          setCode(generated);
+
+          // the next regeneration merges into the code written to the editor.
+          const program = await parseProgram(generated);
+          if (program) {
+            previousCodeRef.current = { source: generated, program };
+          }
         } catch (e) {
           console.warn("⚠️ wasm error:", e);
         }
@@ -25,21 +25,92 @@ const dataTypes = [
     label: "String",
     value: "string",
   },
+  {
+    label: "Text",
+    value: "text",
+  },
   {
     label: "Number",
     value: "number",
   },
+  {
+    label: "BigInt",
+    value: "bigint",
+  },
+  {
+    label: "Float",
+    value: "float",
+  },
+  {
+    label: "Decimal",
+    value: "decimal",
+  },
+  {
+    label: "Boolean",
+    value: "boolean",
+  },
   {
     label: "Date",
     value: "date",
   },
+  {
+    label: "Date (no time)",
+    value: "dateOnly",
+  },
+  {
+    label: "Timestamp (time zone)",
+    value: "timestamptz",
+  },
+  {
+    label: "Time",
+    value: "time",
+  },
+  {
+    label: "Interval",
+    value: "interval",
+  },
   {
     label: "JSON",
     value: "json",
   },
   {
-    label: "Float",
-    value: "float",
+    label: "JSONB",
+    value: "jsonb",
+  },
+  {
+    label: "IP address",
+    value: "inet",
+  },
+  {
+    label: "Binary",
+    value: "binary",
+  },
+  {
+    label: "Enum",
+    value: "enum",
+  },
+];
+
+const generationStrategies = [
+  {
+    label: "None (assigned)",
+    value: "none",
+  },
+  {
+    label: "Increment",
+    value: "increment",
+  },
+  {
+    label: "UUID",
+    value: "uuid",
+  },
+  {
+    label: "Row ID",
+    value: "rowid",
+  },
+  {
+    label: "Identity",
+    value: "identity",
   },
 ];
 
@@ -75,17 +146,17 @@ function ColumnEditorComponent() {
       if (!node) return nds;
       let columns = [...node.data.columns];
 
-      if (editingColumn.primaryKey) node.data.primaryKey = editingColumn.id;
-
       columns = columns.map((col) => {
-        if (col.id !== editingColumn.id) {
-          if (editingColumn.primaryKey) return { ...col, primaryKey: false };
-          return col;
-        }
+        if (col.id !== editingColumn.id) return col;
         return editingColumn;
       });
 
-      return updateNodes({ id: node.id, columns }, nds);
+      // every key column joins the (possibly composite) primary key.
+      const primaryKey = columns
+        .filter((col) => col.primaryKey)
+        .map((col) => col.id);
+
+      return updateNodes({ id: node.id, columns, primaryKey }, nds);
     });
   }, [editingColumn]);
 
@@ -171,11 +242,81 @@ function ColumnEditorComponent() {
                   setEditingColumn({
                     ...editingColumn,
                     primaryKey: !editingColumn.primaryKey,
+                    // a former key keeps no generation strategy.
+                    generated: editingColumn.primaryKey
+                      ? null
+                      : editingColumn.generated,
                   })
                 }
               />
               <Label htmlFor="column-primary-key">Primary Key</Label>
             </div>
+            {/* Generation Strategy */}
+            {!editingColumn.foreignKey && (
+              <div>
+                <Label htmlFor="column-generated">Generation Strategy</Label>
+                <Select
+                  value={editingColumn.generated || "none"}
+                  onValueChange={(e) => {
+                    setEditingColumn({
+                      ...editingColumn,
+                      generated:
+                        e === "none" ? null : (e as ColumnProps["generated"]),
+                    });
+                  }}
+                >
+                  <SelectTrigger id="column-generated">
+                    <SelectValue placeholder="Generation Strategy"></SelectValue>
+                  </SelectTrigger>
+                  <SelectContent>
+                    {generationStrategies.map((strategy) => (
+                      <SelectItem
+                        key={strategy.value}
+                        value={strategy.value}
+                        className="cursor-pointer"
+                      >
+                        {strategy.label}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </div>
+            )}
+            {/* Identity Generation */}
+            {editingColumn.generated === "identity" && (
+              <div>
+                <Label htmlFor="column-identity-generation">
+                  Identity Generation
+                </Label>
+                <Select
+                  value={editingColumn.identityGeneration || "default"}
+                  onValueChange={(e) => {
+                    setEditingColumn({
+                      ...editingColumn,
+                      identityGeneration:
+                        e === "default"
+                          ? null
+                          : (e as ColumnProps["identityGeneration"]),
+                    });
+                  }}
+                >
+                  <SelectTrigger id="column-identity-generation">
+                    <SelectValue placeholder="Identity Generation"></SelectValue>
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="default" className="cursor-pointer">
+                      Database default
+                    </SelectItem>
+                    <SelectItem value="ALWAYS" className="cursor-pointer">
+                      Generated always
+                    </SelectItem>
+                    <SelectItem value="BY DEFAULT" className="cursor-pointer">
+                      Generated by default
+                    </SelectItem>
+                  </SelectContent>
+                </Select>
+              </div>
+            )}
             {/* Auto Increment */}
             <div className="flex items-center gap-2">
               <Checkbox
@@ -261,6 +402,26 @@ function ColumnEditorComponent() {
                   !!editingColumn.unique
                 }
               />
+              <div className="mt-2 flex items-center gap-2">
+                <Checkbox
+                  id="column-default-expression"
+                  checked={editingColumn.defaultKind === "expression"}
+                  disabled={
+                    !!editingColumn.primaryKey ||
+                    !!editingColumn.autoIncrement ||
+                    !!editingColumn.unique
+                  }
+                  onCheckedChange={(checked) =>
+                    setEditingColumn({
+                      ...editingColumn,
+                      defaultKind: checked ? "expression" : "literal",
+                    })
+                  }
+                />
+                <Label htmlFor="column-default-expression">
+                  SQL expression, e.g. now()
+                </Label>
+              </div>
             </div>
             {/* Length */}
             <div>
@@ -278,7 +439,7 @@ function ColumnEditorComponent() {
               />
             </div>
             {/* Precision */}
-            {["float", "number"].includes(editingColumn.dataType) && (
+            {["float", "number", "decimal"].includes(editingColumn.dataType) && (
               <div>
                 <Label htmlFor="column-precision">Precision</Label>
                 <Input
@@ -295,7 +456,7 @@ function ColumnEditorComponent() {
               </div>
             )}
             {/* Scale */}
-            {editingColumn.dataType === "float" && (
+            {["float", "decimal"].includes(editingColumn.dataType) && (
               <div>
                 <Label htmlFor="column-scale">Scale</Label>
                 <Input
@@ -312,7 +473,7 @@ function ColumnEditorComponent() {
               </div>
             )}
             {/* Collation */}
-            {editingColumn.dataType === "string" && (
+            {["string", "text"].includes(editingColumn.dataType) && (
               <div>
                 <Label htmlFor="column-collation">Collation</Label>
                 <Input
@@ -357,7 +518,7 @@ function ColumnEditorComponent() {
               <Label htmlFor="column-zerofill">Zerofill</Label>
             </div>
             {/* Enum */}
-            {editingColumn.dataType === "string" && (
+            {["string", "enum"].includes(editingColumn.dataType) && (
               <>
                 <div>
                   <Label htmlFor="column-enum">Enum</Label>
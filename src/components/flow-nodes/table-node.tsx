@@ -66,12 +66,19 @@ function TableNodeComponent({
         return <ALargeSmall className="w-4 h-4" />;
 
       case "number":
+      case "bigint":
+      case "decimal":
         return <FileDigit className="w-4 h-4" />;
 
       case "date":
+      case "dateOnly":
+      case "timestamptz":
+      case "time":
+      case "interval":
         return <Calendar className="w-4 h-4" />;
 
       case "json":
+      case "jsonb":
         return <Braces className="w-4 h-4" />;
 
       case "float":
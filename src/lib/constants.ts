@@ -1,4 +0,0 @@
-// Monaco imports
-export const TYPEORM_IMPORTS = `import {
-
-} from "typeorm"`;
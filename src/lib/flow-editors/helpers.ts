@@ -22,10 +22,13 @@ export const getDefaultColumn = (
   dbName: "",
   dataType: "string",
   primaryKey: false,
+  generated: null,
+  identityGeneration: null,
   index: false,
   unique: false,
   nullable: false,
   defaultValue: null,
+  defaultKind: "literal",
   length: 255,
   precision: null,
   scale: null,
@@ -33,6 +36,8 @@ export const getDefaultColumn = (
   description: "",
   autoIncrement: false,
   foreignKey: null,
+  embedded: null,
+  checks: [],
   select: true,
   zerofill: false,
   enum: null,
@@ -54,21 +59,33 @@ export const getDefaultTable = (id: string, name: string): TableProps => {
     id: id.toString(),
     name: name,
     dbName: name,
-    primaryKey: "",
+    kind: "entity",
+    extends: null,
+    inheritance: null,
+    tree: null,
+    expression: null,
+    primaryKey: [],
     description: "",
     timestamps: true,
     engine: "InnoDB", // for MySQL only
+    schema: null,
+    database: null,
+    synchronize: true,
+    orderBy: [],
     columns: [],
+    indexes: [],
+    checks: [],
   };
 
   const column = getDefaultColumn(table, {
     name: "id",
     dbName: "id",
     primaryKey: true,
+    generated: "increment",
   });
 
   table.columns.push(column);
-  table.primaryKey = column.id;
+  table.primaryKey = [column.id];
 
   return table;
 };
@@ -8,11 +8,40 @@ export type TableProps = {
   id: string;
   name: string;
   dbName: string; // underscore or pascal transformation of name
-  primaryKey: string; // col id
+  kind: "entity" | "embeddable" | "abstract" | "view"; // embeddables are value objects embedded into entities, abstract tables are base classes
+  extends: InheritanceProps | null; // parent table
+  inheritance: {
+    column: string; // discriminator column of single-table inheritance, e.g. "type"
+    type: string;
+  } | null;
+  tree: "closure-table" | "materialized-path" | "nested-set" | null; // self references become tree parent / children
+  expression: string | null; // SELECT of a view, its columns are the expected result columns
+  primaryKey: string[]; // col ids, more than one for composite keys
   description: string;
   timestamps: boolean;
   engine: "InnoDB" | "MyISAM"; // MySQL only
+  schema: string | null;
+  database: string | null;
+  synchronize: boolean; // false excludes the table from schema sync
+  orderBy: { column: string; order: "ASC" | "DESC" }[]; // col ids, default find order
   columns: ColumnProps[];
+  indexes: IndexProps[];
+  checks: CheckProps[]; // table level check constraints
+};
+
+export type CheckProps = {
+  id: string;
+  name: string | null;
+  expression: string; // SQL boolean expression, e.g. "price" >= 0
+};
+
+export type IndexProps = {
+  id: string;
+  name: string | null;
+  columns: string[]; // col ids, in index order
+  unique: boolean;
+  where: string | null; // partial index condition
+  type: "btree" | "hash" | "gin" | "gist" | "fulltext" | "spatial" | null;
 };
 
 export type ColumnProps = {
@@ -22,17 +51,32 @@ export type ColumnProps = {
   dbName: string; // underscore or pascal transformation of name
   dataType:
   | "string"
+  | "text"
   | "number"
+  | "bigint"
+  | "float"
+  | "decimal"
+  | "boolean"
   | "date"
+  | "dateOnly"
+  | "timestamptz"
+  | "time"
   | "json"
-  | "float"
+  | "jsonb"
+  | "inet"
+  | "interval"
+  | "binary"
+  | "enum"
   | "uuid"
-  | "objectId"; // db types
+  | "objectId"; // logical types, mapped to the native type of the dialect
   primaryKey: boolean;
+  generated: "increment" | "uuid" | "rowid" | "identity" | null; // value generation strategy, null for assigned values
+  identityGeneration: "ALWAYS" | "BY DEFAULT" | null; // GENERATED ... AS IDENTITY, null for the database default
   index: boolean;
   unique: boolean;
   nullable: boolean;
   defaultValue: string | number | boolean | null;
+  defaultKind: "literal" | "expression"; // "expression" defaults are SQL, e.g. now()
   length: number;
   precision: number | null; // total number of digits.
   scale: number | null; // total number of digits after decimal points.
@@ -40,6 +84,8 @@ export type ColumnProps = {
   description: string;
   autoIncrement: boolean; // defaults false
   foreignKey: JoinProps | null;
+  embedded: EmbeddedProps | null; // embeds the columns of an embeddable table
+  checks: CheckProps[];
   select: boolean;
   zerofill: boolean; // MySQL only
   enum: string[] | null;
@@ -48,6 +94,19 @@ export type ColumnProps = {
   array: boolean; // Postgres, cockroachdb
 };
 
+export type InheritanceProps = {
+  table: string; // table id of the parent
+  tableName?: string;
+  strategy: "class-table" | "single-table"; // single-table children share the parent's table
+  discriminator: string | null; // single-table discriminator value, null for the class name
+};
+
+export type EmbeddedProps = {
+  table: string; // table id of the embeddable
+  tableName?: string;
+  prefix: string | false | null; // column name prefix, null for the property name
+};
+
 export type JoinProps = {
   id: string;
   // target: where to get the foreign key from.
@@ -71,3 +130,39 @@ export type JoinProps = {
   } | null; // Which column is bein referenced by the junction table in a ManyToMany join scenario
   type: JoinTypes;
 };
+
+export type CompilerOptions = {
+  inflections?: Record<string, string>; // singular -> plural overrides, e.g. { cactus: "cacti" }
+  previousSource?: string; // code to merge regenerated entities into.
+  previousProgram?: unknown; // ESTree of previousSource, parsed with ranges.
+  sourceMap?: boolean; // return { code, sourceMap } / { nodes, sourceMap }.
+  output?: "code" | "files"; // "files" returns { [path]: code }, one entity per file.
+  fileName?: string; // path template, e.g. "{kebab}.entity.ts", "{pascal}.ts" or "{schema}/{kebab}.entity.ts".
+  barrel?: boolean; // add an index.ts re-exporting every entity file.
+  enumStyle?: "enum" | "union"; // TS enums (default) or string literal union types.
+  dialect?: "postgres" | "mysql" | "sqlite" | "mssql" | "mongodb"; // database the column types are written for.
+  dtoStyle?: "interface" | "class-validator"; // convert_to_typescript DTOs: plain interfaces (default) or validated classes.
+};
+
+// region of generated code emitted for a table or column.
+export type SourceMapEntry = {
+  id: string;
+  kind: "table" | "column";
+  table?: string; // table id of a column.
+  start: number; // byte offsets.
+  end: number;
+  startLine: number; // 1-based, inclusive.
+  endLine: number;
+};
+
+// AST node a table or column was parsed from.
+export type ParsedSourceMapEntry = {
+  id: string;
+  kind: "table" | "column";
+  table?: string;
+  range: [number, number];
+  loc: {
+    start: { line: number; column: number };
+    end: { line: number; column: number };
+  };
+};